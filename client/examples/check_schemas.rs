@@ -0,0 +1,34 @@
+//! Lint a schema configuration without making any requests
+//!
+//! Usage: `cargo run --example check_schemas -- [path/to/schemas.toml]`
+//! Exits non-zero if the file fails to load or has problems, so it can
+//! gate configuration changes in CI.
+
+use client::SchemaManager;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "schemas.toml".to_string());
+
+    let mut schema_manager = SchemaManager::new();
+    if let Err(e) = schema_manager.load_from_file(&path) {
+        eprintln!("{}: failed to load: {}", path, e);
+        return ExitCode::FAILURE;
+    }
+
+    match schema_manager.validate_schemas() {
+        Ok(()) => {
+            println!("{}: OK", path);
+            ExitCode::SUCCESS
+        }
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}: {}", path, error);
+            }
+            eprintln!("{}: {} problem(s) found", path, errors.len());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Common surface of the Spaceflight News API clients
+//!
+//! [`SpaceflightApi`] is implemented by both [`RESTClient`], pointed at a
+//! Spaceflight News API base URL, and [`SpaceDevsClient`], so code that
+//! only needs the content endpoints can be generic over the client.
+
+use crate::client::rest_client::RESTClient;
+use crate::client::spacedevs_client::SpaceDevsClient;
+use crate::endpoints::{article::Article, blog::Blog, paginated::Paginated, report::Report};
+use crate::error::ClientError;
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Typed access to the Spaceflight News API content endpoints
+#[async_trait]
+pub trait SpaceflightApi: Send + Sync {
+    /// Fetch a page of articles
+    async fn articles(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Article>, ClientError>;
+
+    /// Fetch a single article by ID
+    async fn article(&self, id: u32) -> Result<Article, ClientError>;
+
+    /// Fetch a page of blogs
+    async fn blogs(&self, params: &HashMap<String, String>)
+    -> Result<Paginated<Blog>, ClientError>;
+
+    /// Fetch a single blog by ID
+    async fn blog(&self, id: u32) -> Result<Blog, ClientError>;
+
+    /// Fetch a page of reports
+    async fn reports(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Report>, ClientError>;
+
+    /// Fetch a single report by ID
+    async fn report(&self, id: u32) -> Result<Report, ClientError>;
+}
+
+/// Endpoints are resolved against the client's base URL, which must be
+/// the API root, e.g. `SPACEFLIGHT_NEWS_API_BASE`
+#[async_trait]
+impl SpaceflightApi for RESTClient {
+    async fn articles(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Article>, ClientError> {
+        self.get_with_query("articles/", params).await
+    }
+
+    async fn article(&self, id: u32) -> Result<Article, ClientError> {
+        self.get(&format!("articles/{}/", id)).await
+    }
+
+    async fn blogs(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Blog>, ClientError> {
+        self.get_with_query("blogs/", params).await
+    }
+
+    async fn blog(&self, id: u32) -> Result<Blog, ClientError> {
+        self.get(&format!("blogs/{}/", id)).await
+    }
+
+    async fn reports(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Report>, ClientError> {
+        self.get_with_query("reports/", params).await
+    }
+
+    async fn report(&self, id: u32) -> Result<Report, ClientError> {
+        self.get(&format!("reports/{}/", id)).await
+    }
+}
+
+#[async_trait]
+impl SpaceflightApi for SpaceDevsClient {
+    async fn articles(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Article>, ClientError> {
+        self.get_articles(params).await
+    }
+
+    async fn article(&self, id: u32) -> Result<Article, ClientError> {
+        self.get_article(id).await
+    }
+
+    async fn blogs(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Blog>, ClientError> {
+        self.get_blogs(params).await
+    }
+
+    async fn blog(&self, id: u32) -> Result<Blog, ClientError> {
+        self.get_blog(id).await
+    }
+
+    async fn reports(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Report>, ClientError> {
+        self.get_reports(params).await
+    }
+
+    async fn report(&self, id: u32) -> Result<Report, ClientError> {
+        self.get_report(id).await
+    }
+}
@@ -0,0 +1,89 @@
+//! Authentication schemes applied to outgoing requests
+//!
+//! Attach one with [`RESTClient::with_auth`](crate::RESTClient::with_auth).
+//! The public Spaceflight News API needs none, but compatible deployments
+//! may expect a token in a header or an API key in the query string.
+
+use reqwest::RequestBuilder;
+use reqwest::header::HeaderValue;
+use std::fmt;
+
+/// How requests authenticate against the API
+///
+/// The `Debug` output redacts every credential, so an `Auth` can be logged
+/// safely.
+#[derive(Clone, Default)]
+pub enum Auth {
+    /// No authentication
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// `Authorization: Token <token>`, as used by Django REST framework
+    Token(String),
+    /// An API key sent in a custom header
+    ApiKeyHeader {
+        /// Header name
+        name: String,
+        /// API key
+        value: String,
+    },
+    /// An API key sent as a query parameter, added alongside any query the
+    /// request URL already has
+    ApiKeyQuery {
+        /// Query parameter name
+        name: String,
+        /// API key
+        value: String,
+    },
+}
+
+impl Auth {
+    /// Add the credentials to a request
+    pub(crate) fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            Auth::None => request,
+            Auth::Bearer(token) => request.bearer_auth(token),
+            Auth::Token(token) => sensitive_header(
+                request,
+                reqwest::header::AUTHORIZATION.as_str(),
+                &format!("Token {}", token),
+            ),
+            Auth::ApiKeyHeader { name, value } => sensitive_header(request, name, value),
+            Auth::ApiKeyQuery { name, value } => request.query(&[(name, value)]),
+        }
+    }
+}
+
+/// Add a header whose value is hidden from reqwest's own debug output
+fn sensitive_header(request: RequestBuilder, name: &str, value: &str) -> RequestBuilder {
+    match HeaderValue::from_str(value) {
+        Ok(mut header_value) => {
+            header_value.set_sensitive(true);
+            request.header(name, header_value)
+        }
+        // Let the builder report the invalid value when the request is built
+        Err(_) => request.header(name, value),
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const REDACTED: &str = "<redacted>";
+        match self {
+            Auth::None => write!(f, "None"),
+            Auth::Bearer(_) => f.debug_tuple("Bearer").field(&REDACTED).finish(),
+            Auth::Token(_) => f.debug_tuple("Token").field(&REDACTED).finish(),
+            Auth::ApiKeyHeader { name, .. } => f
+                .debug_struct("ApiKeyHeader")
+                .field("name", name)
+                .field("value", &REDACTED)
+                .finish(),
+            Auth::ApiKeyQuery { name, .. } => f
+                .debug_struct("ApiKeyQuery")
+                .field("name", name)
+                .field("value", &REDACTED)
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! In-memory cache of GET response bodies
+//!
+//! Enabled with [`RESTClient::with_cache`](crate::RESTClient::with_cache).
+//! The cache can be saved to and reloaded from a JSON snapshot so a
+//! restarted process doesn't start cold.
+
+use crate::error::ClientError;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A cached response body and when it was fetched
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    body: Bytes,
+    stored_at: SystemTime,
+}
+
+/// One entry of a snapshot file
+#[derive(Serialize, Deserialize)]
+struct SnapshotEntry {
+    url: String,
+    /// Response body; bodies that aren't UTF-8 are left out of snapshots
+    body: String,
+    /// Seconds since the Unix epoch when the body was fetched
+    stored_at: u64,
+}
+
+/// Response bodies by full URL
+#[derive(Debug, Default)]
+pub(crate) struct ResponseCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    /// The body cached for `url`, if it is younger than `ttl` at `now`
+    pub(crate) fn get(&self, url: &str, ttl: Duration, now: SystemTime) -> Option<Bytes> {
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(url)?;
+        is_fresh(entry.stored_at, ttl, now).then(|| entry.body.clone())
+    }
+
+    /// Cache `body` for `url`, fetched at `now`
+    pub(crate) fn insert(&self, url: &str, body: Bytes, now: SystemTime) {
+        self.entries.lock().unwrap().insert(
+            url.to_string(),
+            CachedResponse {
+                body,
+                stored_at: now,
+            },
+        );
+    }
+
+    /// Write every UTF-8 entry to `path` as JSON, returning how many were written
+    pub(crate) fn save(&self, path: &Path) -> Result<usize, ClientError> {
+        let entries = self.entries.lock().unwrap();
+        let snapshot: Vec<SnapshotEntry> = entries
+            .iter()
+            .filter_map(|(url, entry)| {
+                Some(SnapshotEntry {
+                    url: url.clone(),
+                    body: String::from_utf8(entry.body.to_vec()).ok()?,
+                    stored_at: entry
+                        .stored_at
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |age| age.as_secs()),
+                })
+            })
+            .collect();
+        fs::write(path, serde_json::to_vec(&snapshot)?)?;
+        Ok(snapshot.len())
+    }
+
+    /// Add the entries saved at `path` that are still younger than `ttl`
+    ///
+    /// Entries already in memory are replaced by snapshot entries for the
+    /// same URL only if those are newer. Returns how many were loaded.
+    pub(crate) fn load(
+        &self,
+        path: &Path,
+        ttl: Option<Duration>,
+        now: SystemTime,
+    ) -> Result<usize, ClientError> {
+        let snapshot: Vec<SnapshotEntry> = serde_json::from_slice(&fs::read(path)?)?;
+        let mut entries = self.entries.lock().unwrap();
+        let mut loaded = 0;
+        for entry in snapshot {
+            let stored_at = UNIX_EPOCH + Duration::from_secs(entry.stored_at);
+            if ttl.is_some_and(|ttl| !is_fresh(stored_at, ttl, now)) {
+                continue;
+            }
+            if entries
+                .get(&entry.url)
+                .is_some_and(|existing| existing.stored_at >= stored_at)
+            {
+                continue;
+            }
+            entries.insert(
+                entry.url,
+                CachedResponse {
+                    body: Bytes::from(entry.body),
+                    stored_at,
+                },
+            );
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+}
+
+/// Whether something stored at `stored_at` is younger than `ttl` at `now`
+///
+/// Entries from the future, e.g. after the system clock was set back, are
+/// treated as fresh.
+fn is_fresh(stored_at: SystemTime, ttl: Duration, now: SystemTime) -> bool {
+    now.duration_since(stored_at).map_or(true, |age| age < ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn snapshot_round_trip_drops_expired_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache = ResponseCache::default();
+        cache.insert("https://api.test/old", Bytes::from("old"), at(1_000));
+        cache.insert("https://api.test/new", Bytes::from("new"), at(1_050));
+        assert_eq!(cache.save(&path).unwrap(), 2);
+
+        let restored = ResponseCache::default();
+        assert_eq!(restored.load(&path, Some(TTL), at(1_080)).unwrap(), 1);
+        assert_eq!(restored.get("https://api.test/old", TTL, at(1_080)), None);
+        assert_eq!(
+            restored.get("https://api.test/new", TTL, at(1_080)),
+            Some(Bytes::from("new"))
+        );
+
+        // Without a TTL everything is loaded
+        let unbounded = ResponseCache::default();
+        assert_eq!(unbounded.load(&path, None, at(1_080)).unwrap(), 2);
+    }
+
+    #[test]
+    fn newer_entries_in_memory_are_kept_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let saved = ResponseCache::default();
+        saved.insert("https://api.test/a", Bytes::from("saved"), at(1_000));
+        saved.save(&path).unwrap();
+
+        let cache = ResponseCache::default();
+        cache.insert("https://api.test/a", Bytes::from("fresher"), at(1_010));
+        assert_eq!(cache.load(&path, Some(TTL), at(1_020)).unwrap(), 0);
+        assert_eq!(
+            cache.get("https://api.test/a", TTL, at(1_020)),
+            Some(Bytes::from("fresher"))
+        );
+    }
+
+    #[test]
+    fn non_utf8_bodies_are_left_out_of_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let cache = ResponseCache::default();
+        cache.insert(
+            "https://api.test/image",
+            Bytes::from_static(&[0xff, 0xd8, 0xff]),
+            at(1_000),
+        );
+        cache.insert("https://api.test/text", Bytes::from("text"), at(1_000));
+        assert_eq!(cache.save(&path).unwrap(), 1);
+
+        let restored = ResponseCache::default();
+        assert_eq!(restored.load(&path, None, at(1_000)).unwrap(), 1);
+        assert_eq!(restored.get("https://api.test/image", TTL, at(1_000)), None);
+        assert_eq!(
+            restored.get("https://api.test/text", TTL, at(1_000)),
+            Some(Bytes::from("text"))
+        );
+    }
+}
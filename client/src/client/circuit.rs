@@ -0,0 +1,210 @@
+//! Circuit breaker that stops requests to a failing API
+//!
+//! Enabled with
+//! [`RESTClient::with_circuit_breaker`](crate::RESTClient::with_circuit_breaker).
+//! After `failure_threshold` consecutive failed requests the circuit
+//! opens, and requests fail with [`ClientError::CircuitOpen`] without
+//! being sent. Once the cool-down has passed the circuit half-opens: one
+//! request is let through as a probe, closing the circuit if it succeeds
+//! and reopening it for another cool-down if it fails.
+//!
+//! A request counts once, after its retries. Transport failures, `429`
+//! and 5xx responses are failures; any other outcome shows the API is
+//! answering and counts as a success.
+
+use crate::error::ClientError;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Position of the circuit
+#[derive(Debug, Clone, Copy)]
+enum State {
+    /// Requests flow; counts consecutive failures
+    Closed { failures: u32 },
+    /// Requests fail fast until the instant given
+    Open { until: Instant },
+    /// A probe is in flight; other requests fail fast until the instant
+    /// given, after which another probe is allowed in case the first one
+    /// was abandoned
+    HalfOpen { until: Instant },
+}
+
+/// Consecutive-failure circuit breaker shared by a client's requests
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: Duration,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    /// Open after `failure_threshold` consecutive failures (at least one)
+    /// and stay open for `cool_down`
+    pub(crate) fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Admit a request, or fail with `ClientError::CircuitOpen`
+    pub(crate) fn check(&self, now: Instant) -> Result<(), ClientError> {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until } | State::HalfOpen { until } if now < until => {
+                Err(ClientError::CircuitOpen {
+                    retry_after: until - now,
+                })
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                *state = State::HalfOpen {
+                    until: now + self.cool_down,
+                };
+                Ok(())
+            }
+        }
+    }
+
+    /// Record the outcome of an admitted request
+    ///
+    /// Returns `true` if this failure opened the circuit.
+    pub(crate) fn record<T>(&self, result: &Result<T, ClientError>, now: Instant) -> bool {
+        let failed = result.as_ref().is_err_and(is_upstream_failure);
+        let mut state = self.state.lock().unwrap();
+        match (*state, failed) {
+            (_, false) => {
+                *state = State::Closed { failures: 0 };
+                false
+            }
+            (State::Closed { failures }, true) if failures + 1 < self.failure_threshold => {
+                *state = State::Closed {
+                    failures: failures + 1,
+                };
+                false
+            }
+            (State::Open { .. }, true) => false,
+            (_, true) => {
+                *state = State::Open {
+                    until: now + self.cool_down,
+                };
+                true
+            }
+        }
+    }
+}
+
+/// Whether an error means the API is unavailable rather than that the
+/// request was wrong
+fn is_upstream_failure(error: &ClientError) -> bool {
+    match error {
+        ClientError::Api { status, .. } => *status == 429 || *status >= 500,
+        ClientError::Http(_) | ClientError::Transport(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::clock::{Clock, MockClock};
+
+    const COOL_DOWN: Duration = Duration::from_secs(30);
+
+    fn failure() -> Result<(), ClientError> {
+        Err(ClientError::Transport("connection refused".to_string()))
+    }
+
+    /// A breaker opened by three failures, and the clock it was opened at
+    fn opened() -> (CircuitBreaker, MockClock) {
+        let clock = MockClock::new();
+        let breaker = CircuitBreaker::new(3, COOL_DOWN);
+        for _ in 0..3 {
+            breaker.check(clock.now()).unwrap();
+            breaker.record(&failure(), clock.now());
+        }
+        (breaker, clock)
+    }
+
+    fn retry_after(breaker: &CircuitBreaker, clock: &MockClock) -> Option<Duration> {
+        match breaker.check(clock.now()) {
+            Err(ClientError::CircuitOpen { retry_after }) => Some(retry_after),
+            Err(error) => panic!("unexpected error: {error}"),
+            Ok(()) => None,
+        }
+    }
+
+    #[test]
+    fn opens_after_the_failure_threshold() {
+        let clock = MockClock::new();
+        let breaker = CircuitBreaker::new(3, COOL_DOWN);
+        assert!(!breaker.record(&failure(), clock.now()));
+        assert!(!breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), None);
+        assert!(breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), Some(COOL_DOWN));
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let clock = MockClock::new();
+        let breaker = CircuitBreaker::new(2, COOL_DOWN);
+        breaker.record(&failure(), clock.now());
+        breaker.record(&Ok(()), clock.now());
+        assert!(!breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), None);
+    }
+
+    #[test]
+    fn client_errors_count_as_success() {
+        let clock = MockClock::new();
+        let breaker = CircuitBreaker::new(1, COOL_DOWN);
+        let rejected: Result<(), _> = Err(ClientError::InvalidQuery("bad".to_string()));
+        assert!(!breaker.record(&rejected, clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), None);
+    }
+
+    #[test]
+    fn fails_fast_while_open() {
+        let (breaker, clock) = opened();
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(retry_after(&breaker, &clock), Some(Duration::from_secs(20)));
+        // Failures reported by requests admitted before opening change nothing
+        assert!(!breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn lets_one_probe_through_after_the_cool_down() {
+        let (breaker, clock) = opened();
+        clock.advance(COOL_DOWN);
+        assert_eq!(retry_after(&breaker, &clock), None);
+        // Other requests wait while the probe is in flight
+        assert_eq!(retry_after(&breaker, &clock), Some(COOL_DOWN));
+        // An abandoned probe is replaced after another cool-down
+        clock.advance(COOL_DOWN);
+        assert_eq!(retry_after(&breaker, &clock), None);
+    }
+
+    #[test]
+    fn successful_probe_closes_the_circuit() {
+        let (breaker, clock) = opened();
+        clock.advance(COOL_DOWN);
+        breaker.check(clock.now()).unwrap();
+        assert!(!breaker.record(&Ok(()), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), None);
+        // The full threshold is needed to open it again
+        assert!(!breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), None);
+    }
+
+    #[test]
+    fn failed_probe_reopens_the_circuit() {
+        let (breaker, clock) = opened();
+        clock.advance(COOL_DOWN);
+        breaker.check(clock.now()).unwrap();
+        clock.advance(Duration::from_secs(1));
+        assert!(breaker.record(&failure(), clock.now()));
+        assert_eq!(retry_after(&breaker, &clock), Some(COOL_DOWN));
+    }
+}
@@ -0,0 +1,94 @@
+//! Time source for the REST client
+//!
+//! The client reads the time to measure request latency and waits between
+//! retries through a [`Clock`]. [`SystemClock`] uses real time; swap in a
+//! [`MockClock`] with
+//! [`RESTClient::with_clock`](crate::RESTClient::with_clock) to exercise
+//! retry delays without actually sleeping.
+
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of the current time and of delays
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// The current instant
+    fn now(&self) -> Instant;
+
+    /// The current wall-clock time, for timestamps that outlive the process
+    fn system_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    /// Wait for `duration`
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Real time, sleeping on the tokio timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Virtual time that only moves when told to
+///
+/// `sleep` returns immediately after advancing the clock by the requested
+/// duration, so `elapsed` shows how long the client would have waited.
+#[derive(Debug)]
+pub struct MockClock {
+    start: Instant,
+    start_system: SystemTime,
+    elapsed: Mutex<Duration>,
+}
+
+impl MockClock {
+    /// Create a clock at virtual time zero
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            start_system: SystemTime::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+    /// Virtual time passed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn system_time(&self) -> SystemTime {
+        self.start_system + self.elapsed()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -0,0 +1,67 @@
+//! Conditional GET support
+//!
+//! Keep the [`Validators`] from a response and pass them to
+//! [`RESTClient::get_conditional`](crate::RESTClient::get_conditional) on
+//! the next request; if the resource is unchanged the server answers
+//! `304 Not Modified` without resending the body.
+
+use reqwest::RequestBuilder;
+use reqwest::header::{ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+
+/// Cache validators a server sent with a response
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    /// `ETag` header
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// `Last-Modified` header
+    #[serde(default)]
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Read the validators from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    /// Whether there is nothing to make a request conditional on
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Add `If-None-Match`/`If-Modified-Since` headers to a request
+    pub(crate) fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+/// Result of a conditional GET
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// The resource changed, or the request was unconditional
+    Modified {
+        /// Decoded body
+        value: T,
+        /// Validators to send next time
+        validators: Validators,
+    },
+    /// The server answered `304 Not Modified`
+    NotModified,
+}
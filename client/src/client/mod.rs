@@ -1 +1,18 @@
+pub mod api;
+pub mod auth;
+pub(crate) mod cache;
+pub(crate) mod circuit;
+pub mod clock;
+pub mod conditional;
+pub mod observer;
+pub(crate) mod outstanding;
+pub mod pagination;
+pub mod query;
+pub mod rate_limit;
 pub mod rest_client;
+pub mod retry;
+pub mod search;
+pub mod spacedevs_client;
+pub mod transport;
+#[cfg(feature = "vcr")]
+pub mod vcr;
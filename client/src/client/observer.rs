@@ -0,0 +1,34 @@
+//! Hooks for observing what the REST client is doing
+//!
+//! Implement [`RequestObserver`] and attach it with
+//! [`RESTClient::with_observer`](crate::RESTClient::with_observer) to route
+//! client diagnostics into your own logging or metrics pipeline.
+
+use std::time::Duration;
+
+/// A completed request, successful or not
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    /// Full request URL
+    pub url: String,
+    /// HTTP status, or `None` when no response was received
+    pub status: Option<u16>,
+    /// Size of the response body in bytes
+    pub bytes: u64,
+    /// Time from sending the request until the body was read, including retries
+    pub latency: Duration,
+    /// Whether the request produced a usable response
+    pub success: bool,
+}
+
+/// Receives notifications from a `RESTClient`
+pub trait RequestObserver: Send + Sync {
+    /// Called after every request completes
+    fn on_request(&self, _event: &RequestEvent) {}
+
+    /// Called when the client detects a recoverable problem, such as a
+    /// paginated fetch that returned fewer items than the API reported
+    fn on_warning(&self, message: &str) {
+        eprintln!("Warning: {}", message);
+    }
+}
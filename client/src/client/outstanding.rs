@@ -0,0 +1,63 @@
+//! Count of requests awaiting a response, for draining on shutdown
+//!
+//! [`RESTClient::shutdown`](crate::RESTClient::shutdown) closes the count
+//! to new requests and waits for it to reach zero.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tokio::sync::Notify;
+
+/// Requests sent and not yet answered, and whether new ones are refused
+#[derive(Debug, Default)]
+pub(crate) struct Outstanding {
+    count: AtomicUsize,
+    closed: AtomicBool,
+    idle: Notify,
+}
+
+impl Outstanding {
+    /// Count a request until the returned guard is dropped, or return
+    /// `None` once closed
+    pub(crate) fn start(&self) -> Option<Guard<'_>> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        if self.closed.load(Ordering::SeqCst) {
+            self.finish();
+            return None;
+        }
+        Some(Guard(self))
+    }
+
+    /// Refuse new requests from now on
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Wait until no request is outstanding
+    pub(crate) async fn drained(&self) {
+        loop {
+            let idle = self.idle.notified();
+            tokio::pin!(idle);
+            // Register before checking, so a wake-up between the check and
+            // the wait is not lost
+            idle.as_mut().enable();
+            if self.count.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    fn finish(&self) {
+        if self.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Keeps one request counted while alive
+pub(crate) struct Guard<'a>(&'a Outstanding);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
@@ -0,0 +1,45 @@
+//! Pagination parameter styles
+//!
+//! The Spaceflight News API pages with `limit`/`offset`, but some
+//! compatible mirrors use Django REST framework's `page`/`page_size`
+//! instead. [`PaginationStyle`] decides which parameters the client and
+//! [`ContentQuery`](crate::ContentQuery) emit.
+
+/// How a paginated endpoint selects a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaginationStyle {
+    /// `limit` items starting at `offset`
+    #[default]
+    LimitOffset,
+    /// `page_size` items on 1-based page `page`
+    PageNumber,
+}
+
+impl PaginationStyle {
+    /// Name of the page size parameter
+    pub fn size_param(self) -> &'static str {
+        match self {
+            PaginationStyle::LimitOffset => "limit",
+            PaginationStyle::PageNumber => "page_size",
+        }
+    }
+
+    /// Parameters selecting `limit` items starting at item `offset`
+    ///
+    /// For `PageNumber`, `offset` is rounded down to the start of the page
+    /// containing it.
+    pub fn params(self, limit: u32, offset: u32) -> [(&'static str, String); 2] {
+        match self {
+            PaginationStyle::LimitOffset => {
+                [("limit", limit.to_string()), ("offset", offset.to_string())]
+            }
+            PaginationStyle::PageNumber => {
+                let limit = limit.max(1);
+                [
+                    ("page_size", limit.to_string()),
+                    ("page", (offset / limit + 1).to_string()),
+                ]
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Builder for the Spaceflight News API's content list filters
+//!
+//! [`ContentQuery`] produces the parameter map taken by
+//! [`SpaceDevsClient::get_articles_with`](crate::SpaceDevsClient::get_articles_with)
+//! and friends. The articles, blogs and reports endpoints accept the same
+//! filters, so one builder serves all three. [`LaunchQuery`] does the same
+//! for the Launch Library 2 launch lists.
+
+use crate::client::pagination::PaginationStyle;
+use crate::error::ClientError;
+use crate::schema::DEFAULT_MAX_LIMIT;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
+
+/// Query parameters for a content list endpoint
+#[derive(Debug, Clone, Default)]
+pub struct ContentQuery {
+    params: HashMap<String, String>,
+    pagination: PaginationStyle,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+/// The name this builder had when it only served articles
+pub type ArticleQuery = ContentQuery;
+
+impl ContentQuery {
+    /// Create an empty query
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an arbitrary parameter
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Emit paging parameters in `style` instead of `limit`/`offset`
+    pub fn pagination(mut self, style: PaginationStyle) -> Self {
+        self.pagination = style;
+        self
+    }
+
+    /// Number of results per page
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Number of results to skip
+    ///
+    /// With page-number pagination this selects the page containing the
+    /// item at `offset`, using the `limit` as the page size.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Full-text search in titles and summaries
+    pub fn search(self, search: impl Into<String>) -> Self {
+        self.param("search", search)
+    }
+
+    /// Sort order, e.g. `-published_at`
+    pub fn ordering(self, ordering: impl Into<String>) -> Self {
+        self.param("ordering", ordering)
+    }
+
+    /// Only items published between `start` and `end`, inclusive
+    ///
+    /// Emits the single `published_at__range=start,end` parameter rather
+    /// than separate `__gte`/`__lte` bounds. Timestamps are sent as RFC 3339
+    /// in UTC with second precision (`2025-01-01T00:00:00Z`), and the comma
+    /// is URL-encoded along with the rest of the value when the request is
+    /// built. Fails if `start` is after `end`.
+    pub fn published_range(
+        self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Self, ClientError> {
+        if start > end {
+            return Err(ClientError::InvalidQuery(format!(
+                "published_at__range start {} is after end {}",
+                format_timestamp(start),
+                format_timestamp(end)
+            )));
+        }
+        let range = format!("{},{}", format_timestamp(start), format_timestamp(end));
+        Ok(self.param("published_at__range", range))
+    }
+
+    /// Only items that are (or aren't) linked to a launch
+    ///
+    /// Articles and blogs only; the reports endpoint has no launch filter.
+    pub fn has_launch(self, has_launch: bool) -> Self {
+        self.param("has_launch", has_launch.to_string())
+    }
+
+    /// Only items that are (or aren't) linked to an event
+    ///
+    /// Articles and blogs only; the reports endpoint has no event filter.
+    pub fn has_event(self, has_event: bool) -> Self {
+        self.param("has_event", has_event.to_string())
+    }
+
+    /// The parameters, ready to pass to a list fetch
+    ///
+    /// Paging is rendered in the configured style. An offset without a
+    /// limit uses the API's maximum page size when paging by number.
+    pub fn params(&self) -> HashMap<String, String> {
+        let mut params = self.params.clone();
+        match (self.pagination, self.limit, self.offset) {
+            (_, None, None) => {}
+            (PaginationStyle::LimitOffset, limit, offset) => {
+                if let Some(limit) = limit {
+                    params.insert("limit".to_string(), limit.to_string());
+                }
+                if let Some(offset) = offset {
+                    params.insert("offset".to_string(), offset.to_string());
+                }
+            }
+            (PaginationStyle::PageNumber, limit, offset) => {
+                let limit = limit.unwrap_or(DEFAULT_MAX_LIMIT);
+                for (name, value) in self.pagination.params(limit, offset.unwrap_or(0)) {
+                    params.insert(name.to_string(), value);
+                }
+            }
+        }
+        params
+    }
+}
+
+impl From<ContentQuery> for HashMap<String, String> {
+    fn from(query: ContentQuery) -> Self {
+        query.params()
+    }
+}
+
+/// Query parameters for the Launch Library 2 upcoming and previous launch
+/// lists
+#[derive(Debug, Clone, Default)]
+pub struct LaunchQuery {
+    params: HashMap<String, String>,
+}
+
+impl LaunchQuery {
+    /// Create an empty query
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an arbitrary parameter
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Number of results per page
+    pub fn limit(self, limit: u32) -> Self {
+        self.param("limit", limit.to_string())
+    }
+
+    /// Number of results to skip
+    pub fn offset(self, offset: u32) -> Self {
+        self.param("offset", offset.to_string())
+    }
+
+    /// Only launches by the launch service provider named `provider`,
+    /// e.g. `SpaceX`
+    pub fn provider(self, provider: impl Into<String>) -> Self {
+        self.param("lsp__name", provider)
+    }
+
+    /// Full-text search in launch, rocket and mission names
+    pub fn search(self, search: impl Into<String>) -> Self {
+        self.param("search", search)
+    }
+
+    /// The parameters, ready to pass to a list fetch
+    pub fn params(&self) -> HashMap<String, String> {
+        self.params.clone()
+    }
+}
+
+impl From<LaunchQuery> for HashMap<String, String> {
+    fn from(query: LaunchQuery) -> Self {
+        query.params
+    }
+}
+
+/// Format a timestamp the way the API's date filters expect
+pub(crate) fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::rest_client::encode_query;
+    use crate::schema::QueryEncoding;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn published_range_is_one_encoded_parameter() {
+        let params = ContentQuery::new()
+            .published_range(at(2025, 1, 1, 0), at(2025, 1, 31, 12))
+            .unwrap()
+            .params();
+        assert_eq!(
+            params["published_at__range"],
+            "2025-01-01T00:00:00Z,2025-01-31T12:00:00Z"
+        );
+        assert_eq!(
+            encode_query(&params, QueryEncoding::Percent),
+            "published_at__range=2025-01-01T00%3A00%3A00Z%2C2025-01-31T12%3A00%3A00Z"
+        );
+    }
+
+    #[test]
+    fn published_range_drops_subseconds() {
+        let start = at(2025, 1, 1, 0) + chrono::Duration::milliseconds(250);
+        let params = ContentQuery::new()
+            .published_range(start, at(2025, 1, 2, 0))
+            .unwrap()
+            .params();
+        assert_eq!(
+            params["published_at__range"],
+            "2025-01-01T00:00:00Z,2025-01-02T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn published_range_rejects_reversed_dates() {
+        let error = ContentQuery::new()
+            .published_range(at(2025, 2, 1, 0), at(2025, 1, 1, 0))
+            .unwrap_err();
+        assert!(matches!(error, ClientError::InvalidQuery(_)));
+        assert_eq!(
+            error.to_string(),
+            "Invalid query: published_at__range start 2025-02-01T00:00:00Z is after end 2025-01-01T00:00:00Z"
+        );
+
+        // A single instant is a valid range
+        assert!(
+            ContentQuery::new()
+                .published_range(at(2025, 1, 1, 0), at(2025, 1, 1, 0))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Spacing requests to stay under an API's rate limit
+//!
+//! The Launch Library 2 API allows only a few requests per hour without a
+//! key. A [`RateLimiter`] shared between concurrent tasks hands out start
+//! slots at a fixed interval, so callers can fetch concurrently and still
+//! respect the limit.
+
+use crate::client::clock::{Clock, SystemClock};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Evenly spaced permits for at most `rate` requests per second
+pub struct RateLimiter {
+    /// Time between two permits
+    interval: Duration,
+    /// Earliest instant the next permit may be handed out
+    next: Mutex<Option<Instant>>,
+    /// Time source for waiting
+    clock: Arc<dyn Clock>,
+}
+
+impl RateLimiter {
+    /// Allow `rate` requests per second, e.g. `0.25` for one every four
+    /// seconds
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not a positive, finite number.
+    pub fn new(rate: f64) -> Self {
+        assert!(
+            rate.is_finite() && rate > 0.0,
+            "rate must be a positive number of requests per second"
+        );
+        Self {
+            interval: Duration::from_secs_f64(1.0 / rate),
+            next: Mutex::new(None),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Wait through `clock` instead of real time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Time between two permits
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Wait until the caller may send its next request
+    ///
+    /// The first permit is immediate. Each call reserves the next free
+    /// slot before waiting, so concurrent callers get successive slots.
+    pub async fn acquire(&self) {
+        let now = self.clock.now();
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = next.map_or(now, |next| next.max(now));
+            *next = Some(slot + self.interval);
+            slot
+        };
+        let wait = slot.saturating_duration_since(now);
+        if !wait.is_zero() {
+            self.clock.sleep(wait).await;
+        }
+    }
+}
@@ -1,34 +1,173 @@
-use crate::schema::SchemaManager;
-use reqwest::{Client, Error};
+use crate::client::auth::Auth;
+use crate::client::cache::ResponseCache;
+use crate::client::circuit::CircuitBreaker;
+use crate::client::clock::{Clock, SystemClock};
+use crate::client::conditional::{Conditional, Validators};
+use crate::client::observer::{RequestEvent, RequestObserver};
+use crate::client::outstanding::{Guard, Outstanding};
+use crate::client::pagination::PaginationStyle;
+use crate::client::rate_limit::RateLimiter;
+use crate::client::retry::{Backoff, ResponseInfo, RetryBudget, RetryPolicy};
+use crate::client::transport::{HttpTransport, Transport};
+use crate::error::ClientError;
+use crate::schema::{DEFAULT_MAX_LIMIT, QueryEncoding, SchemaManager};
+use crate::utils::deserialize::optional_link;
+use bytes::Bytes;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
 
 /// Response structure for paginated API endpoints
+///
+/// `next` and `previous` are usually URLs, but mirrors using page-number
+/// pagination may send a bare page number instead.
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct PaginatedResponse<T> {
     pub count: u32,
+    #[serde(default, deserialize_with = "optional_link")]
     pub next: Option<String>,
+    #[serde(default, deserialize_with = "optional_link")]
     pub previous: Option<String>,
     pub results: Vec<T>,
 }
 
+/// A page whose items were decoded one by one
+///
+/// Items that failed to decode are left out of `results` and reported in
+/// `errors` instead of failing the whole page.
+#[derive(Debug)]
+pub struct LenientPage<T> {
+    pub count: u32,
+    pub next: Option<String>,
+    pub previous: Option<String>,
+    pub results: Vec<T>,
+    pub errors: Vec<ItemError>,
+}
+
+/// Items collected by `get_all_before`
+#[derive(Debug)]
+pub struct Partial<T> {
+    /// Items of the pages fetched before the deadline, in page order
+    pub items: Vec<T>,
+    /// Whether the deadline passed before the last page was fetched
+    pub cut_short: bool,
+}
+
+/// Body returned by `get_range`
+#[derive(Debug, Clone)]
+pub enum Ranged {
+    /// The server sent the requested range (`206 Partial Content`)
+    Partial(Bytes),
+    /// The server ignored `Range` and sent the whole body from the start
+    /// (`200 OK`); appending it to a partial download would corrupt it
+    Full(Bytes),
+}
+
+/// An item of a page that could not be decoded
+#[derive(Debug)]
+pub struct ItemError {
+    /// Position of the item in the page's `results`
+    pub index: usize,
+    /// Why decoding failed
+    pub error: serde_json::Error,
+}
+
+/// Callback receiving the number of items fetched so far and the API's `count`
+pub type ProgressCallback = Arc<dyn Fn(usize, u32) + Send + Sync>;
+
+/// Options controlling how `get_all` walks a paginated endpoint
+#[derive(Clone, Default)]
+pub struct GetAllOptions {
+    /// Fail instead of warning when pagination ends before `count` items
+    /// have been collected
+    pub strict_count: bool,
+    /// Stop after this many items. Pages are requested with a `limit` of at
+    /// most the API maximum, so asking for more than one page's worth is
+    /// satisfied by following `next` rather than by an oversized request.
+    /// A `limit` already in the endpoint's query is replaced.
+    pub max_items: Option<usize>,
+    /// Stop fetching when this token is cancelled. The page in flight is
+    /// abandoned and `get_all` returns `ClientError::Cancelled`; items
+    /// collected so far are discarded.
+    pub cancel: Option<CancellationToken>,
+    /// Called after each page with the running item count and the
+    /// `count` the API reported on that page
+    pub on_progress: Option<ProgressCallback>,
+    /// Most retries allowed across all pages of the walk. Each page is
+    /// still retried per the client's retry policy, but once this many
+    /// retries have been spent, the next failure ends the walk. Unlimited
+    /// when `None`.
+    pub retry_budget: Option<u32>,
+}
+
+impl GetAllOptions {
+    /// Report progress to `callback` after each page
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, u32) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl std::fmt::Debug for GetAllOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GetAllOptions")
+            .field("strict_count", &self.strict_count)
+            .field("max_items", &self.max_items)
+            .field("cancel", &self.cancel)
+            .field("on_progress", &self.on_progress.is_some())
+            .field("retry_budget", &self.retry_budget)
+            .finish()
+    }
+}
+
+/// Outcome of a request, as handed to callers waiting on the same URL
+type SharedResult = Result<Bytes, Arc<ClientError>>;
+
+/// Requests currently on the wire, keyed by full URL
+type InFlight = Mutex<HashMap<String, broadcast::Sender<SharedResult>>>;
+
+/// Response size from which JSON is decoded on the blocking thread pool
+pub const DEFAULT_BLOCKING_DECODE_THRESHOLD: usize = 1024 * 1024;
+
 /// A generic REST API client that can work with any RESTful API
 pub struct RESTClient {
     client: Arc<Client>,
     base_url: String,
     schema_manager: Option<SchemaManager>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    transport: Arc<dyn Transport>,
+    retry_policy: RetryPolicy,
+    auth: Auth,
+    max_response_bytes: Option<u64>,
+    pagination: PaginationStyle,
+    http2_prior_knowledge: bool,
+    http2_adaptive_window: bool,
+    blocking_decode_threshold: usize,
+    cache_ttl: Option<Duration>,
+    cache: ResponseCache,
+    circuit: Option<CircuitBreaker>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    clock: Arc<dyn Clock>,
+    in_flight: InFlight,
+    outstanding: Outstanding,
 }
 
 impl RESTClient {
     /// Create a new RESTClient with the specified base URL
     pub fn new(base_url: impl Into<String>) -> Self {
-        Self {
-            client: Arc::new(Client::new()),
-            base_url: base_url.into(),
-            schema_manager: None,
-        }
+        Self::with_client(base_url, Client::new())
     }
 
     /// Create a new RESTClient with custom configuration
@@ -37,15 +176,247 @@ impl RESTClient {
             client: Arc::new(client),
             base_url: base_url.into(),
             schema_manager: None,
+            observer: None,
+            transport: Arc::new(HttpTransport),
+            retry_policy: RetryPolicy::default(),
+            auth: Auth::None,
+            max_response_bytes: None,
+            pagination: PaginationStyle::default(),
+            http2_prior_knowledge: false,
+            http2_adaptive_window: false,
+            blocking_decode_threshold: DEFAULT_BLOCKING_DECODE_THRESHOLD,
+            cache_ttl: None,
+            cache: ResponseCache::default(),
+            circuit: None,
+            rate_limiter: None,
+            clock: Arc::new(SystemClock),
+            in_flight: Mutex::new(HashMap::new()),
+            outstanding: Outstanding::default(),
         }
     }
 
     /// Create a new RESTClient with schema support
     pub fn with_schemas(base_url: impl Into<String>, schema_manager: SchemaManager) -> Self {
         Self {
-            client: Arc::new(Client::new()),
-            base_url: base_url.into(),
             schema_manager: Some(schema_manager),
+            ..Self::new(base_url)
+        }
+    }
+
+    /// Attach an observer that receives client diagnostics
+    pub fn with_observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Route requests through a custom transport instead of the network
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Retry failed requests up to `max_retries` times, waiting `delay`
+    /// between attempts
+    ///
+    /// Sets a `Backoff::Fixed` delay, replacing any earlier `with_backoff`;
+    /// call `with_backoff` afterwards for another strategy.
+    pub fn with_retries(mut self, max_retries: u32, delay: Duration) -> Self {
+        self.retry_policy.max_retries = max_retries;
+        self.retry_policy.backoff = Backoff::Fixed(delay);
+        self
+    }
+
+    /// Wait between retries according to `backoff`
+    ///
+    /// A `Retry-After` header on the failed response takes precedence, up
+    /// to `with_max_retry_after`, so this applies to transport failures and
+    /// to 429 and 5xx responses that do not say how long to wait.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.retry_policy.backoff = backoff;
+        self
+    }
+
+    /// Wait at most `max` when a response's `Retry-After` asks for longer
+    ///
+    /// Without this, the cap is the backoff's longest delay, so a server
+    /// asking for an hour cannot stall a retry loop for that long.
+    pub fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.retry_policy.max_retry_after = Some(max);
+        self
+    }
+
+    /// Stop sending requests after `failure_threshold` consecutive
+    /// failures, for `cool_down`
+    ///
+    /// While open, requests fail with `ClientError::CircuitOpen`; after the
+    /// cool-down one probe request decides whether the circuit closes or
+    /// opens again. A request counts once, after its retries, and only
+    /// transport failures, 429 and 5xx responses count as failures.
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, cool_down: Duration) -> Self {
+        self.circuit = Some(CircuitBreaker::new(failure_threshold, cool_down));
+        self
+    }
+
+    /// Space every request attempt, retries included, through `limiter`
+    ///
+    /// The limiter can be shared with other clients that must stay under
+    /// the same API's rate limit. Requests answered from the cache or
+    /// shared with a concurrent caller don't take a permit.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Decide which failures are retried
+    ///
+    /// The predicate sees the status, the parsed error detail and the
+    /// attempt number. It replaces the default, which retries transport
+    /// failures, 429 and 5xx responses.
+    pub fn with_retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&ResponseInfo) -> bool + Send + Sync + 'static,
+    {
+        self.retry_policy.predicate = Arc::new(predicate);
+        self
+    }
+
+    /// Replace the whole retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Authenticate every request with `auth`
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Fail with `ClientError::TooLarge` instead of reading response bodies
+    /// larger than `max_response_bytes`
+    ///
+    /// The body is read in chunks and abandoned as soon as the limit is
+    /// passed, so an oversized response is never buffered in full.
+    /// Unbounded by default.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: u64) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Page with `style`'s parameters instead of `limit`/`offset`
+    ///
+    /// Controls the parameters `get_all` and `get_all_parallel` send, and
+    /// lets `get_all` follow `next` values that are bare page numbers.
+    pub fn with_pagination(mut self, style: PaginationStyle) -> Self {
+        self.pagination = style;
+        self
+    }
+
+    /// Decode JSON responses of at least `bytes` on tokio's blocking pool
+    ///
+    /// Parsing a large body inline holds up every other task on the same
+    /// worker thread, while handing it to `spawn_blocking` costs a thread
+    /// hand-off of a few microseconds. The default,
+    /// [`DEFAULT_BLOCKING_DECODE_THRESHOLD`] (1 MiB), keeps ordinary pages
+    /// (100 articles is roughly 150 KiB) inline and offloads only unusually
+    /// large bodies; lower it when many large pages are decoded
+    /// concurrently. `usize::MAX` always decodes inline.
+    pub fn with_blocking_decode_threshold(mut self, bytes: usize) -> Self {
+        self.blocking_decode_threshold = bytes;
+        self
+    }
+
+    /// Cache GET response bodies in memory for `ttl`
+    ///
+    /// Cached URLs are answered without contacting the API until the entry
+    /// is `ttl` old. Applies to the fetches that return decoded data, not
+    /// to `get_conditional`, `get_to_writer` or `post`. Off by default.
+    pub fn with_cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Save the response cache to `path` as JSON
+    ///
+    /// Returns the number of entries written. Bodies that aren't valid
+    /// UTF-8 are skipped.
+    pub fn save_cache_snapshot(&self, path: impl AsRef<Path>) -> Result<usize, ClientError> {
+        self.cache.save(path.as_ref())
+    }
+
+    /// Load entries saved by `save_cache_snapshot` into the response cache
+    ///
+    /// Entries older than the cache TTL are dropped; with no TTL set,
+    /// everything is loaded, though nothing is served until `with_cache`
+    /// enables the cache. Returns the number of entries loaded.
+    pub fn load_cache_snapshot(&self, path: impl AsRef<Path>) -> Result<usize, ClientError> {
+        self.cache
+            .load(path.as_ref(), self.cache_ttl, self.clock.system_time())
+    }
+
+    /// Read the time and wait between retries through `clock`
+    ///
+    /// Defaults to [`SystemClock`]; a [`MockClock`](crate::MockClock)
+    /// makes retry delays instant and observable.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Speak HTTP/2 from the first byte instead of negotiating it
+    ///
+    /// Only works against servers known to accept cleartext HTTP/2 or
+    /// HTTP/2 over TLS without ALPN; anything else fails to connect. Over
+    /// HTTPS, reqwest already negotiates HTTP/2 when the server offers it,
+    /// which is the default. Multiplexing helps `get_all_parallel` against
+    /// a CDN that limits connections per client; with few concurrent pages,
+    /// HTTP/1.1 over the connection pool performs about the same.
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, replacing one passed to
+    /// `with_client`.
+    pub fn with_http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.http2_prior_knowledge = enabled;
+        self.rebuild_client()
+    }
+
+    /// Let HTTP/2 flow-control windows grow with measured bandwidth
+    ///
+    /// Helps large pages over high-latency links once HTTP/2 is in use;
+    /// has no effect on HTTP/1.1 connections. Off by default.
+    ///
+    /// Rebuilds the underlying `reqwest::Client`, replacing one passed to
+    /// `with_client`.
+    pub fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = enabled;
+        self.rebuild_client()
+    }
+
+    /// Build a fresh `reqwest::Client` from the HTTP/2 settings
+    fn rebuild_client(mut self) -> Self {
+        let mut builder = Client::builder().http2_adaptive_window(self.http2_adaptive_window);
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        self.client = Arc::new(
+            builder
+                .build()
+                .expect("failed to initialize the HTTP client"),
+        );
+        self
+    }
+
+    /// Largest page size the API accepts, from the schema manager if set
+    pub fn max_limit(&self) -> u32 {
+        self.schema_manager
+            .as_ref()
+            .map_or(DEFAULT_MAX_LIMIT, SchemaManager::max_limit)
+    }
+
+    /// Report a recoverable problem to the observer, or stderr if none is set
+    fn warn(&self, message: &str) {
+        match &self.observer {
+            Some(observer) => observer.on_warning(message),
+            None => eprintln!("Warning: {}", message),
         }
     }
 
@@ -54,6 +425,26 @@ impl RESTClient {
         &self.client
     }
 
+    /// Stop sending requests, wait up to `timeout` for those in flight,
+    /// then drop the client
+    ///
+    /// Consumes this handle to the client, which is usually shared between
+    /// tasks as an `Arc<RESTClient>`, for example one being replaced on a
+    /// config reload. Requests made through any other handle after this
+    /// fail with `ClientError::Cancelled`. Returns `true` once every
+    /// request already sent has had its body read (or failed), or `false`
+    /// if some were still running when `timeout`, as measured by the
+    /// client's clock, ran out. The connection pool closes when the last
+    /// handle is dropped; a `Client` passed to `with_client` that is still
+    /// cloned elsewhere keeps its pool open.
+    pub async fn shutdown(self: Arc<Self>, timeout: Duration) -> bool {
+        self.outstanding.close();
+        tokio::select! {
+            _ = self.outstanding.drained() => true,
+            _ = self.clock.sleep(timeout) => false,
+        }
+    }
+
     /// Build a full URL for an endpoint
     fn build_url(&self, endpoint: &str) -> String {
         format!(
@@ -63,21 +454,986 @@ impl RESTClient {
         )
     }
 
+    /// Get the schema manager, if one is configured
+    pub fn schema_manager(&self) -> Option<&SchemaManager> {
+        self.schema_manager.as_ref()
+    }
+
+    /// How query values are escaped: the schema manager's encoding, or
+    /// percent-encoding without one
+    pub fn query_encoding(&self) -> QueryEncoding {
+        self.schema_manager
+            .as_ref()
+            .map_or_else(QueryEncoding::default, SchemaManager::query_encoding)
+    }
+
+    /// Get the schema manager, failing if none is configured
+    fn schemas(&self) -> Result<&SchemaManager, ClientError> {
+        self.schema_manager
+            .as_ref()
+            .ok_or(ClientError::NoSchemaManager)
+    }
+
+    /// Count a request as in flight for `shutdown` until the guard is
+    /// dropped, or fail with `ClientError::Cancelled` after `shutdown`
+    fn start_request(&self) -> Result<Guard<'_>, ClientError> {
+        self.outstanding.start().ok_or(ClientError::Cancelled)
+    }
+
+    /// Send a GET request for a full URL, retrying per the retry policy
+    async fn send(&self, url: &str) -> Result<Response, ClientError> {
+        self.send_with(url, None, None).await
+    }
+
+    /// Send a GET request, conditional on `validators` when given
+    ///
+    /// Each retry is also taken from `budget`, if given; when it runs out
+    /// the failure is returned.
+    async fn send_with(
+        &self,
+        url: &str,
+        validators: Option<&Validators>,
+        budget: Option<&RetryBudget>,
+    ) -> Result<Response, ClientError> {
+        self.send_retrying(url, budget, validators.is_some(), || {
+            let request = self.auth.apply(self.client.get(url));
+            match validators {
+                Some(validators) => validators.apply(request),
+                None => request,
+            }
+        })
+        .await
+    }
+
+    /// Send the request built by `request`, rebuilding it for each retry
+    ///
+    /// `304 Not Modified` counts as success when `allow_not_modified` is
+    /// set. Each retry is also taken from `budget`, if given. With a
+    /// circuit breaker, fails fast while it is open and records the final
+    /// outcome. Callers count the request with `start_request` until its
+    /// body has been read.
+    async fn send_retrying<F>(
+        &self,
+        url: &str,
+        budget: Option<&RetryBudget>,
+        allow_not_modified: bool,
+        request: F,
+    ) -> Result<Response, ClientError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        if let Some(circuit) = &self.circuit {
+            circuit.check(self.clock.now())?;
+        }
+
+        let mut attempt = 1;
+        let result = loop {
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+            match self.send_once(request(), allow_not_modified).await {
+                Err(e) if self.retry_policy.should_retry(&e, attempt) => {
+                    if let Some(budget) = budget
+                        && !budget.try_take()
+                    {
+                        self.warn(&format!("retry budget exhausted, giving up on {}", url));
+                        break Err(e);
+                    }
+                    self.clock.sleep(self.retry_delay(&e, attempt)).await;
+                    attempt += 1;
+                }
+                result => break result,
+            }
+        };
+
+        if let Some(circuit) = &self.circuit
+            && circuit.record(&result, self.clock.now())
+        {
+            self.warn(&format!(
+                "circuit opened after repeated failures, last on {}",
+                url
+            ));
+        }
+        result
+    }
+
+    /// Wait before retrying after `error` ended `attempt`: the
+    /// `Retry-After` the API or circuit breaker gave, capped per the retry
+    /// policy, or the backoff
+    fn retry_delay(&self, error: &ClientError, attempt: u32) -> Duration {
+        match error {
+            ClientError::Api {
+                retry_after: Some(retry_after),
+                ..
+            }
+            | ClientError::CircuitOpen { retry_after } => {
+                self.retry_policy.clamp_retry_after(*retry_after)
+            }
+            _ => self.retry_policy.backoff.delay(attempt),
+        }
+    }
+
+    /// Send a single request through the transport
+    ///
+    /// Non-2xx responses are turned into `ClientError::Api` using the
+    /// error body the API sent, except `304 Not Modified` when
+    /// `allow_not_modified` is set.
+    async fn send_once(
+        &self,
+        request: RequestBuilder,
+        allow_not_modified: bool,
+    ) -> Result<Response, ClientError> {
+        let response = self
+            .transport
+            .execute(&self.client, request.build()?)
+            .await?;
+
+        let status = response.status();
+        let not_modified = allow_not_modified && status == reqwest::StatusCode::NOT_MODIFIED;
+        if status.is_success() || not_modified {
+            Ok(response)
+        } else {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| parse_retry_after(value, self.clock.system_time()));
+            let body = response.text().await.unwrap_or_default();
+            let mut error = ClientError::from_response_body(status, &body);
+            if let ClientError::Api {
+                retry_after: slot, ..
+            } = &mut error
+            {
+                *slot = retry_after;
+            }
+            Err(error)
+        }
+    }
+
+    /// Fetch the full body of a URL, sharing the request with concurrent
+    /// callers asking for the same URL
+    ///
+    /// The first caller performs the request; callers arriving while it is
+    /// in flight wait for its result instead of hitting the API again. If
+    /// the first caller is dropped before finishing, waiters fall back to
+    /// their own request.
+    async fn fetch_bytes(
+        &self,
+        url: &str,
+        budget: Option<&RetryBudget>,
+    ) -> Result<Bytes, ClientError> {
+        if let Some(ttl) = self.cache_ttl
+            && let Some(body) = self.cache.get(url, ttl, self.clock.system_time())
+        {
+            return Ok(body);
+        }
+
+        let waiting = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(url) {
+                Some(sender) => Some(sender.subscribe()),
+                None => {
+                    in_flight.insert(url.to_string(), broadcast::channel(1).0);
+                    None
+                }
+            }
+        };
+
+        if let Some(mut receiver) = waiting {
+            return match receiver.recv().await {
+                Ok(result) => result.map_err(|e| e.duplicate()),
+                Err(_) => self.fetch_bytes_uncoalesced(url, budget).await,
+            };
+        }
+
+        let flight = Flight {
+            in_flight: &self.in_flight,
+            url,
+            landed: false,
+        };
+        let result = self.fetch_bytes_uncoalesced(url, budget).await;
+        let shared = match &result {
+            Ok(body) => Ok(body.clone()),
+            Err(e) => Err(Arc::new(e.duplicate())),
+        };
+        if let Some(sender) = flight.land() {
+            // No receivers just means nobody else asked for this URL
+            let _ = sender.send(shared);
+        }
+        result
+    }
+
+    /// Fetch the full body of a URL, reporting the request to the observer
+    async fn fetch_bytes_uncoalesced(
+        &self,
+        url: &str,
+        budget: Option<&RetryBudget>,
+    ) -> Result<Bytes, ClientError> {
+        let body = self
+            .fetch_reported(url, self.send_with(url, None, budget))
+            .await?
+            .body;
+        if self.cache_ttl.is_some() {
+            self.cache
+                .insert(url, body.clone(), self.clock.system_time());
+        }
+        Ok(body)
+    }
+
+    /// Read the response `send` produces and report it to the observer
+    ///
+    /// Latency runs from this call until the body is read. A failure is
+    /// reported with the API's status where there was one.
+    async fn fetch_reported(
+        &self,
+        url: &str,
+        send: impl Future<Output = Result<Response, ClientError>>,
+    ) -> Result<Fetched, ClientError> {
+        let started = self.clock.now();
+        let result = async {
+            let _request = self.start_request()?;
+            let response = send.await?;
+            let status = response.status().as_u16();
+            let headers = response.headers().clone();
+            let body = self.read_body(response).await?;
+            Ok(Fetched {
+                status,
+                headers,
+                body,
+            })
+        }
+        .await;
+
+        let (status, bytes) = match &result {
+            Ok(fetched) => (Some(fetched.status), fetched.body.len() as u64),
+            Err(ClientError::Api { status, .. }) => (Some(*status), 0),
+            Err(_) => (None, 0),
+        };
+        self.report(url, started, status, bytes, result.is_ok());
+        result
+    }
+
+    /// Send a request to the observer, if one is set
+    fn report(&self, url: &str, started: Instant, status: Option<u16>, bytes: u64, success: bool) {
+        if let Some(observer) = &self.observer {
+            observer.on_request(&RequestEvent {
+                url: url.to_string(),
+                status,
+                bytes,
+                latency: self.clock.now().saturating_duration_since(started),
+                success,
+            });
+        }
+    }
+
+    /// Read a response body, enforcing `max_response_bytes`
+    async fn read_body(&self, mut response: Response) -> Result<Bytes, ClientError> {
+        let Some(limit) = self.max_response_bytes else {
+            return Ok(response.bytes().await?);
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > limit)
+        {
+            return Err(ClientError::TooLarge { limit });
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() as u64 + chunk.len() as u64 > limit {
+                return Err(ClientError::TooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(Bytes::from(body))
+    }
+
+    /// Resolve a page's `next` value against the URL that returned it
+    ///
+    /// URLs are followed as-is. Under page-number pagination a bare page
+    /// number replaces the `page` parameter of `current`.
+    fn next_url(&self, current: &str, next: String) -> String {
+        if self.pagination != PaginationStyle::PageNumber || next.parse::<u64>().is_err() {
+            return next;
+        }
+
+        match reqwest::Url::parse(current) {
+            Ok(mut url) => {
+                let pairs: Vec<(String, String)> = url
+                    .query_pairs()
+                    .filter(|(name, _)| name != "page")
+                    .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                    .collect();
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(pairs)
+                    .append_pair("page", &next);
+                url.to_string()
+            }
+            Err(_) => append_query(current.to_string(), &format!("page={}", next)),
+        }
+    }
+
+    /// Decode a JSON body, on the blocking pool if it is large
+    async fn decode<T>(&self, body: Bytes) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        if body.len() < self.blocking_decode_threshold {
+            return Ok(serde_json::from_slice(&body)?);
+        }
+        match tokio::task::spawn_blocking(move || serde_json::from_slice(&body)).await {
+            Ok(decoded) => Ok(decoded?),
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => Err(ClientError::Transport(format!("decode task failed: {}", e))),
+        }
+    }
+
+    /// Fetch a URL and decode its JSON body
+    async fn fetch_json<T>(&self, url: &str) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.fetch_json_within(url, None).await
+    }
+
+    /// Fetch a URL and decode its JSON body, taking retries from `budget`
+    async fn fetch_json_within<T>(
+        &self,
+        url: &str,
+        budget: Option<&RetryBudget>,
+    ) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let body = self.fetch_bytes(url, budget).await?;
+        self.decode(body).await
+    }
+
     /// Fetch data from an endpoint and deserialize it
-    pub async fn get<T>(&self, endpoint: &str) -> Result<T, Error>
+    pub async fn get<T>(&self, endpoint: &str) -> Result<T, ClientError>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
     {
         let url = self.build_url(endpoint);
-        let response = self.client.get(&url).send().await?;
-        response.json::<T>().await
+        self.fetch_json(&url).await
     }
 
-    /// Fetch data from an endpoint and return raw JSON
-    pub async fn get_json(&self, endpoint: &str) -> Result<Value, Error> {
+    /// Fetch data from an endpoint, returning the exact response body too
+    ///
+    /// The `Bytes` are what the API sent, before any decoding, for
+    /// archiving originals or reproducing parse problems.
+    pub async fn get_with_raw<T>(&self, endpoint: &str) -> Result<(T, Bytes), ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
         let url = self.build_url(endpoint);
-        let response = self.client.get(&url).send().await?;
-        response.json::<Value>().await
+        let body = self.fetch_bytes(&url, None).await?;
+        let value = self.decode(body.clone()).await?;
+        Ok((value, body))
+    }
+
+    /// Fetch an endpoint's raw body and `Content-Type`, without decoding
+    ///
+    /// Sent with the same auth, headers, timeout, retries and size limit
+    /// as `get`, for endpoints that aren't JSON or to see exactly what the
+    /// server sent when decoding fails. Never served from the response
+    /// cache or shared with concurrent callers, since those keep only the
+    /// body.
+    pub async fn get_bytes(&self, endpoint: &str) -> Result<(Bytes, Option<String>), ClientError> {
+        let url = self.build_url(endpoint);
+        let fetched = self
+            .fetch_reported(&url, self.send_with(&url, None, None))
+            .await?;
+        let content_type = fetched.content_type();
+        Ok((fetched.body, content_type))
+    }
+
+    /// Fetch a full URL outside the API, such as an article's image
+    ///
+    /// Like `get_bytes`, but `url` is used as given and no auth is sent,
+    /// since such URLs are served by other hosts. The transport, retries,
+    /// circuit breaker, observer and size limit still apply.
+    pub(crate) async fn get_external_bytes(
+        &self,
+        url: &str,
+    ) -> Result<(Bytes, Option<String>), ClientError> {
+        let send = self.send_retrying(url, None, false, || self.client.get(url));
+        let fetched = self.fetch_reported(url, send).await?;
+        let content_type = fetched.content_type();
+        Ok((fetched.body, content_type))
+    }
+
+    /// Fetch bytes `start..=end` of an endpoint's body, or from `start` to
+    /// the end if `end` is `None`
+    ///
+    /// Sends a `Range` header, for resuming an interrupted download such
+    /// as one written with `get_to_writer`. Servers that don't support
+    /// ranges answer with the whole body, which is returned as
+    /// `Ranged::Full` so it isn't mistaken for the missing part. A range
+    /// past the end fails with a `416` `ClientError::Api`, and a partial
+    /// response whose `Content-Range` doesn't start at `start` with
+    /// `ClientError::RangeMismatch`. An `end` before `start` is rejected
+    /// without sending anything. Never served from the response cache or
+    /// shared with concurrent callers.
+    pub async fn get_range(
+        &self,
+        endpoint: &str,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<Ranged, ClientError> {
+        if let Some(end) = end
+            && end < start
+        {
+            return Err(ClientError::InvalidQuery(format!(
+                "range end {} is before start {}",
+                end, start
+            )));
+        }
+        let url = self.build_url(endpoint);
+        let range = match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        };
+        let send = self.send_retrying(&url, None, false, || {
+            self.auth
+                .apply(self.client.get(&url))
+                .header(reqwest::header::RANGE, &range)
+        });
+        let fetched = self.fetch_reported(&url, send).await?;
+        if fetched.status != 206 {
+            return Ok(Ranged::Full(fetched.body));
+        }
+
+        let content_range = fetched
+            .headers
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok());
+        if content_range.and_then(content_range_start) != Some(start) {
+            return Err(ClientError::RangeMismatch {
+                start,
+                content_range: content_range.map(str::to_string),
+            });
+        }
+        Ok(Ranged::Partial(fetched.body))
+    }
+
+    /// Fetch data from an endpoint unless it is unchanged since `validators`
+    ///
+    /// Sends `If-None-Match`/`If-Modified-Since` from `validators` and
+    /// returns `Conditional::NotModified` on `304`. Otherwise the body is
+    /// decoded and returned with the response's validators for next time.
+    /// Conditional requests are never shared with concurrent callers.
+    pub async fn get_conditional<T>(
+        &self,
+        endpoint: &str,
+        validators: &Validators,
+    ) -> Result<Conditional<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = self.build_url(endpoint);
+        let started = self.clock.now();
+        let result = async {
+            let _request = self.start_request()?;
+            let response = self.send_with(&url, Some(validators), None).await?;
+            let status = response.status().as_u16();
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                return Ok((status, None));
+            }
+            let validators = Validators::from_headers(response.headers());
+            let body = self.read_body(response).await?;
+            Ok((status, Some((body, validators))))
+        }
+        .await;
+
+        let (status, bytes) = match &result {
+            Ok((status, body)) => (
+                Some(*status),
+                body.as_ref().map_or(0, |(body, _)| body.len() as u64),
+            ),
+            Err(ClientError::Api { status, .. }) => (Some(*status), 0),
+            Err(_) => (None, 0),
+        };
+        self.report(&url, started, status, bytes, result.is_ok());
+
+        match result? {
+            (_, None) => Ok(Conditional::NotModified),
+            (_, Some((body, validators))) => Ok(Conditional::Modified {
+                value: self.decode(body).await?,
+                validators,
+            }),
+        }
+    }
+
+    /// POST `body` as JSON to an endpoint and deserialize the response
+    ///
+    /// Uses the same auth, retry policy and observer as GETs, so a failed
+    /// POST may be sent again; only use this for endpoints where that is
+    /// harmless, such as searches. POSTs are never shared with concurrent
+    /// callers.
+    pub async fn post<B, T>(&self, endpoint: &str, body: &B) -> Result<T, ClientError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = self.build_url(endpoint);
+        let body = Bytes::from(serde_json::to_vec(body)?);
+        let send = self.send_retrying(&url, None, false, || {
+            self.auth
+                .apply(self.client.post(&url))
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+        });
+        let fetched = self.fetch_reported(&url, send).await?;
+        self.decode(fetched.body).await
+    }
+
+    /// Fetch data from an endpoint, treating 404 Not Found as `None`
+    ///
+    /// Any other failure is still an error.
+    pub async fn get_optional<T>(&self, endpoint: &str) -> Result<Option<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        match self.get(endpoint).await {
+            Ok(value) => Ok(Some(value)),
+            Err(ClientError::Api { status: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Stream an endpoint's response body into `writer`
+    ///
+    /// The body is written chunk by chunk as it arrives, so large responses
+    /// are never held in memory. `on_bytes` is called after each chunk with
+    /// the bytes written so far and the `Content-Length`, or `None` if the
+    /// server did not send one. `max_response_bytes` applies. Returns the
+    /// number of bytes written; the writer is flushed but not shut down.
+    pub async fn get_to_writer<W>(
+        &self,
+        endpoint: &str,
+        writer: &mut W,
+        on_bytes: Option<&(dyn Fn(u64, Option<u64>) + Send + Sync)>,
+    ) -> Result<u64, ClientError>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let url = self.build_url(endpoint);
+        let started = self.clock.now();
+        let mut status = None;
+        let mut written = 0;
+
+        let result = async {
+            let _request = self.start_request()?;
+            let mut response = self.send(&url).await?;
+            status = Some(response.status().as_u16());
+            let total = response.content_length();
+            if let (Some(limit), Some(total)) = (self.max_response_bytes, total)
+                && total > limit
+            {
+                return Err(ClientError::TooLarge { limit });
+            }
+
+            while let Some(chunk) = response.chunk().await? {
+                if let Some(limit) = self.max_response_bytes
+                    && written + chunk.len() as u64 > limit
+                {
+                    return Err(ClientError::TooLarge { limit });
+                }
+                writer.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+                if let Some(on_bytes) = on_bytes {
+                    on_bytes(written, total);
+                }
+            }
+            writer.flush().await?;
+            Ok(written)
+        }
+        .await;
+
+        let status = match &result {
+            Err(ClientError::Api { status, .. }) => Some(*status),
+            _ => status,
+        };
+        self.report(&url, started, status, written, result.is_ok());
+
+        result
+    }
+
+    /// Fetch every page of a paginated endpoint by following `next` links
+    pub async fn get_all<T>(&self, endpoint: &str) -> Result<Vec<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.get_all_with_options(endpoint, &GetAllOptions::default())
+            .await
+    }
+
+    /// Fetch every page of a paginated endpoint with explicit options
+    ///
+    /// The API's `count` can drift while pages are being walked, so the
+    /// number of collected items is compared against the last reported
+    /// `count` (capped at `max_items`). A shortfall is reported as a
+    /// warning, or as an error when `strict_count` is set.
+    ///
+    /// The returned future only touches local state, so besides using
+    /// `cancel` it is also safe to drop from a `tokio::select!` branch.
+    pub async fn get_all_with_options<T>(
+        &self,
+        endpoint: &str,
+        options: &GetAllOptions,
+    ) -> Result<Vec<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut url = self.build_url(endpoint);
+        if let Some(max_items) = options.max_items {
+            let page_size = max_items.clamp(1, self.max_limit() as usize);
+            url = replace_query_param(url, self.pagination.size_param(), &page_size.to_string());
+        }
+
+        let budget = options.retry_budget.map(RetryBudget::new);
+        let mut items = Vec::new();
+        let mut expected;
+
+        loop {
+            let fetch = self.fetch_json_within(&url, budget.as_ref());
+            let page: PaginatedResponse<T> = match &options.cancel {
+                Some(cancel) => tokio::select! {
+                    _ = cancel.cancelled() => return Err(ClientError::Cancelled),
+                    page = fetch => page?,
+                },
+                None => fetch.await?,
+            };
+            expected = page.count as usize;
+            items.extend(page.results);
+            if let Some(on_progress) = &options.on_progress {
+                on_progress(
+                    items.len().min(options.max_items.unwrap_or(usize::MAX)),
+                    page.count,
+                );
+            }
+
+            if let Some(max_items) = options.max_items
+                && items.len() >= max_items
+            {
+                items.truncate(max_items);
+                break;
+            }
+
+            match page.next {
+                Some(next) => url = self.next_url(&url, next),
+                None => break,
+            }
+        }
+
+        if let Some(max_items) = options.max_items {
+            expected = expected.min(max_items);
+        }
+
+        if items.len() < expected {
+            let message = format!(
+                "pagination of '{}' ended after {} of {} reported items",
+                endpoint,
+                items.len(),
+                expected
+            );
+            if options.strict_count {
+                return Err(ClientError::IncompletePagination {
+                    collected: items.len(),
+                    expected,
+                });
+            }
+            self.warn(&message);
+        }
+
+        Ok(items)
+    }
+
+    /// Fetch every page of a paginated endpoint until `deadline`
+    ///
+    /// `query` is sent with the first page; `next` is followed after
+    /// that. A page that fails with an error the retry predicate accepts,
+    /// or because the circuit is open, is fetched again after the usual
+    /// delay for as long as the deadline allows, whatever `max_retries`
+    /// says. Other errors are returned.
+    ///
+    /// When the deadline passes, the request in flight is dropped and the
+    /// items of the pages already fetched are returned with `cut_short`
+    /// set. The deadline is read on the client's clock, which also times
+    /// the retry delays, so a retry that would end past it is not waited
+    /// for.
+    pub async fn get_all_before<T>(
+        &self,
+        endpoint: &str,
+        query: &HashMap<String, String>,
+        deadline: Instant,
+    ) -> Result<Partial<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let mut items = Vec::new();
+        let mut url = append_query(
+            self.build_url(endpoint),
+            &encode_query(query, self.query_encoding()),
+        );
+        loop {
+            let mut attempt = 1;
+            let page: PaginatedResponse<T> = loop {
+                let remaining = deadline.saturating_duration_since(self.clock.now());
+                if remaining.is_zero() {
+                    return Ok(Partial {
+                        items,
+                        cut_short: true,
+                    });
+                }
+                let fetched = tokio::select! {
+                    biased;
+                    result = self.fetch_json(&url) => result,
+                    _ = self.clock.sleep(remaining) => {
+                        return Ok(Partial { items, cut_short: true });
+                    }
+                };
+                match fetched {
+                    Err(e) if self.is_transient(&e, attempt) => {
+                        let delay = self.retry_delay(&e, attempt);
+                        // No point waiting for a retry the deadline forbids
+                        if delay >= deadline.saturating_duration_since(self.clock.now()) {
+                            return Ok(Partial {
+                                items,
+                                cut_short: true,
+                            });
+                        }
+                        self.clock.sleep(delay).await;
+                        attempt += 1;
+                    }
+                    result => break result?,
+                }
+            };
+            items.extend(page.results);
+
+            match page.next {
+                Some(next) => url = self.next_url(&url, next),
+                None => {
+                    return Ok(Partial {
+                        items,
+                        cut_short: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Whether a failed page fetch may succeed if tried again
+    fn is_transient(&self, error: &ClientError, attempt: u32) -> bool {
+        match error {
+            ClientError::Http(_) | ClientError::Transport(_) | ClientError::Api { .. } => {
+                (self.retry_policy.predicate)(&ResponseInfo::from_error(error, attempt))
+            }
+            ClientError::CircuitOpen { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Fetch every page of a paginated endpoint concurrently by offset
+    ///
+    /// The first page supplies `count`; the remaining pages are then
+    /// requested by `offset` (or `page`, per the pagination style), up to `concurrency` at a time, and
+    /// concatenated in offset order. Pages use the page size from `query`,
+    /// capped at the API maximum, or the maximum if none is given.
+    ///
+    /// If items are added or removed while pages are being fetched, offsets
+    /// shift: a page may repeat items from its neighbour or come back short.
+    /// Repeats are removed by their `id` field, keeping the first occurrence;
+    /// items without an `id` are kept as-is. Items that shifted past the
+    /// pages fetched are not retried, so use `get_all` when an exact
+    /// snapshot matters more than speed.
+    pub async fn get_all_parallel<T>(
+        &self,
+        endpoint: &str,
+        query: &HashMap<String, String>,
+        concurrency: usize,
+    ) -> Result<Vec<T>, ClientError>
+    where
+        T: DeserializeOwned,
+    {
+        let max_limit = self.max_limit();
+        let limit = query
+            .get(self.pagination.size_param())
+            .and_then(|limit| limit.parse::<u32>().ok())
+            .map_or(max_limit, |limit| limit.clamp(1, max_limit));
+
+        let page_url = |offset: u32| {
+            let mut params = query.clone();
+            for (name, value) in self.pagination.params(limit, offset) {
+                params.insert(name.to_string(), value);
+            }
+            append_query(
+                self.build_url(endpoint),
+                &encode_query(&params, self.query_encoding()),
+            )
+        };
+
+        let first: PaginatedResponse<Value> = self.fetch_json(&page_url(0)).await?;
+        let offsets = (limit..first.count).step_by(limit as usize);
+        let mut pages: Vec<(u32, Vec<Value>)> = stream::iter(offsets)
+            .map(|offset| {
+                let url = page_url(offset);
+                async move {
+                    let page: PaginatedResponse<Value> = self.fetch_json(&url).await?;
+                    Ok::<_, ClientError>((offset, page.results))
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .try_collect()
+            .await?;
+        pages.sort_by_key(|(offset, _)| *offset);
+
+        let mut seen = HashSet::new();
+        std::iter::once(first.results)
+            .chain(pages.into_iter().map(|(_, results)| results))
+            .flatten()
+            .filter(|item| match item.get("id") {
+                Some(id) => seen.insert(id.to_string()),
+                None => true,
+            })
+            .map(|item| serde_json::from_value(item).map_err(ClientError::from))
+            .collect()
+    }
+
+    /// Fetch data from an endpoint and return raw JSON
+    pub async fn get_json(&self, endpoint: &str) -> Result<Value, ClientError> {
+        self.get(endpoint).await
+    }
+
+    /// Fetch an endpoint with query parameters and deserialize it
+    ///
+    /// Parameters are URL-encoded as given; no schema defaults or
+    /// validation are applied.
+    pub async fn get_with_query<T>(
+        &self,
+        endpoint: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = append_query(
+            self.build_url(endpoint),
+            &encode_query(params, self.query_encoding()),
+        );
+        self.fetch_json(&url).await
+    }
+
+    /// Fetch one page of an endpoint, skipping items that fail to decode
+    ///
+    /// The page envelope must still be valid; only the elements of
+    /// `results` are decoded individually. Their decode errors are
+    /// returned with the item's index so rejects can be logged.
+    pub async fn get_lenient_page<T>(
+        &self,
+        endpoint: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<LenientPage<T>, ClientError>
+    where
+        T: DeserializeOwned,
+    {
+        let page: PaginatedResponse<Value> = self.get_with_query(endpoint, params).await?;
+
+        let mut results = Vec::with_capacity(page.results.len());
+        let mut errors = Vec::new();
+        for (index, item) in page.results.into_iter().enumerate() {
+            match serde_json::from_value(item) {
+                Ok(item) => results.push(item),
+                Err(error) => errors.push(ItemError { index, error }),
+            }
+        }
+
+        Ok(LenientPage {
+            count: page.count,
+            next: page.next,
+            previous: page.previous,
+            results,
+            errors,
+        })
+    }
+
+    /// Stream the pages of a paginated endpoint, one `results` list at a time
+    ///
+    /// Each page is requested only when the stream is polled for it, so
+    /// at most one page is held at a time. The stream ends after the last
+    /// page, or after the first error.
+    pub fn stream_pages<'a, T>(
+        &'a self,
+        endpoint: &str,
+        query: &HashMap<String, String>,
+    ) -> impl Stream<Item = Result<Vec<T>, ClientError>> + 'a
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = append_query(
+            self.build_url(endpoint),
+            &encode_query(query, self.query_encoding()),
+        );
+        stream::try_unfold(Some(url), move |url| async move {
+            let Some(url) = url else {
+                return Ok(None);
+            };
+            let page: PaginatedResponse<T> = self.fetch_json(&url).await?;
+            let next = page.next.map(|next| self.next_url(&url, next));
+            Ok(Some((page.results, next)))
+        })
+    }
+
+    /// Fetch pages of an endpoint while `predicate` holds for each item
+    ///
+    /// Items are checked in the order the API returns them, and fetching
+    /// stops at the first item the predicate rejects; that item and
+    /// everything after it are dropped. This only makes sense when `query`
+    /// orders the results by the field the predicate tests, e.g.
+    /// `ordering=-published_at` with a "published in the last 30 days"
+    /// predicate, so that once one item fails no later item can pass.
+    pub async fn get_while<T, F>(
+        &self,
+        endpoint: &str,
+        query: &HashMap<String, String>,
+        predicate: F,
+    ) -> Result<Vec<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+        F: Fn(&T) -> bool,
+    {
+        let mut url = append_query(
+            self.build_url(endpoint),
+            &encode_query(query, self.query_encoding()),
+        );
+        let mut items = Vec::new();
+
+        loop {
+            let page: PaginatedResponse<T> = self.fetch_json(&url).await?;
+            for item in page.results {
+                if !predicate(&item) {
+                    return Ok(items);
+                }
+                items.push(item);
+            }
+
+            match page.next {
+                Some(next) => url = self.next_url(&url, next),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// Fetch raw JSON from an endpoint with query parameters, without a schema
+    pub async fn get_json_with_params(
+        &self,
+        endpoint: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<Value, ClientError> {
+        self.get_with_query(endpoint, params).await
     }
 
     /// Fetch data from an endpoint and apply a schema to it
@@ -85,13 +1441,24 @@ impl RESTClient {
         &self,
         endpoint: &str,
         schema_name: &str,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
-        if let Some(schema_manager) = &self.schema_manager {
-            let json_data = self.get_json(endpoint).await?;
-            schema_manager.apply_schema(schema_name, &json_data)
-        } else {
-            Err("No schema manager configured".into())
-        }
+    ) -> Result<Value, ClientError> {
+        let schema_manager = self.schemas()?;
+        let json_data = self.get_json(endpoint).await?;
+        Ok(schema_manager.apply_schema(schema_name, &json_data)?)
+    }
+
+    /// Fetch data from an endpoint keeping only the fields a schema
+    /// declares
+    ///
+    /// See `SchemaManager::project` for how nested fields are handled.
+    pub async fn get_projected(
+        &self,
+        endpoint: &str,
+        schema_name: &str,
+    ) -> Result<Value, ClientError> {
+        let schema_manager = self.schemas()?;
+        let json_data = self.get_json(endpoint).await?;
+        Ok(schema_manager.project(schema_name, &json_data)?)
     }
 
     /// Fetch data from an endpoint with query parameters defined in schema
@@ -100,18 +1467,14 @@ impl RESTClient {
         endpoint: &str,
         schema_name: &str,
         params: &HashMap<String, String>,
-    ) -> Result<T, Box<dyn std::error::Error>>
+    ) -> Result<T, ClientError>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
     {
-        if let Some(schema_manager) = &self.schema_manager {
-            let query_string = schema_manager.build_query_string(schema_name, params)?;
-            let url = format!("{}{}", self.build_url(endpoint), query_string);
-            let response = self.client.get(&url).send().await?;
-            Ok(response.json::<T>().await?)
-        } else {
-            Err("No schema manager configured".into())
-        }
+        let schema_manager = self.schemas()?;
+        let query_string = schema_manager.build_query_string(schema_name, params)?;
+        let url = format!("{}{}", self.build_url(endpoint), query_string);
+        self.fetch_json(&url).await
     }
 
     /// Fetch data from an endpoint with query parameters and apply schema
@@ -120,15 +1483,645 @@ impl RESTClient {
         endpoint: &str,
         schema_name: &str,
         params: &HashMap<String, String>,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
-        if let Some(schema_manager) = &self.schema_manager {
-            let query_string = schema_manager.build_query_string(schema_name, params)?;
-            let url = format!("{}{}", self.build_url(endpoint), query_string);
-            let response = self.client.get(&url).send().await?;
-            let json_data = response.json::<Value>().await?;
-            schema_manager.apply_schema(schema_name, &json_data)
-        } else {
-            Err("No schema manager configured".into())
+    ) -> Result<Value, ClientError> {
+        let schema_manager = self.schemas()?;
+        let json_data: Value = self.get_with_params(endpoint, schema_name, params).await?;
+        Ok(schema_manager.apply_schema(schema_name, &json_data)?)
+    }
+}
+
+/// A response read to the end
+struct Fetched {
+    status: u16,
+    headers: reqwest::header::HeaderMap,
+    body: Bytes,
+}
+
+impl Fetched {
+    /// The `Content-Type` header, if present and readable
+    fn content_type(&self) -> Option<String> {
+        self.headers
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+}
+
+/// First byte of a `Content-Range` such as `bytes 100-199/1000`
+fn content_range_start(content_range: &str) -> Option<u64> {
+    let (start, _) = content_range.strip_prefix("bytes ")?.split_once('-')?;
+    start.trim().parse().ok()
+}
+
+/// Registration of a request in `RESTClient::in_flight`
+///
+/// Removes the entry when dropped so an abandoned request does not leave
+/// later callers waiting on a result that never comes.
+struct Flight<'a> {
+    in_flight: &'a InFlight,
+    url: &'a str,
+    landed: bool,
+}
+
+impl Flight<'_> {
+    /// Deregister the request and return the channel to its waiters
+    fn land(mut self) -> Option<broadcast::Sender<SharedResult>> {
+        self.landed = true;
+        self.in_flight.lock().unwrap().remove(self.url)
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        if !self.landed
+            && let Ok(mut in_flight) = self.in_flight.lock()
+        {
+            in_flight.remove(self.url);
+        }
+    }
+}
+
+/// URL-encode query parameters as `key=value` pairs joined by `&`, sorted
+/// by key so equal parameters always give the same URL
+pub(crate) fn encode_query(params: &HashMap<String, String>, encoding: QueryEncoding) -> String {
+    let mut pairs: Vec<_> = params.iter().collect();
+    pairs.sort();
+    pairs
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", encoding.escape(key), encoding.escape(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Read a `Retry-After` value, either a number of seconds or an HTTP date
+///
+/// A date in the past gives a zero wait.
+fn parse_retry_after(value: &str, now: std::time::SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let date = std::time::SystemTime::from(date);
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Append an already-encoded query string to a URL that may have one
+fn append_query(url: String, query: &str) -> String {
+    if query.is_empty() {
+        url
+    } else if url.contains('?') {
+        format!("{}&{}", url, query)
+    } else {
+        format!("{}?{}", url, query)
+    }
+}
+
+/// Set `name` to `value` in a URL's query, replacing any values it already
+/// has there
+fn replace_query_param(url: String, name: &str, value: &str) -> String {
+    let (base, query) = url.split_once('?').unwrap_or((&url, ""));
+    let mut pairs: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(name))
+        .collect();
+    let param = format!("{}={}", name, value);
+    pairs.push(&param);
+    format!("{}?{}", base, pairs.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::clock::MockClock;
+    use async_trait::async_trait;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A canned response: status, headers and body
+    type Reply = (u16, Vec<(&'static str, String)>, String);
+
+    /// Transport serving canned responses in order, logging the URL of
+    /// each request and the virtual time it was sent at
+    struct Scripted {
+        clock: Arc<MockClock>,
+        replies: Mutex<VecDeque<Reply>>,
+        log: Mutex<Vec<(String, Duration)>>,
+    }
+
+    impl Scripted {
+        fn new(clock: Arc<MockClock>, replies: Vec<Reply>) -> Arc<Self> {
+            Arc::new(Self {
+                clock,
+                replies: Mutex::new(replies.into()),
+                log: Mutex::new(Vec::new()),
+            })
+        }
+
+        /// Virtual times at which requests were sent
+        fn times(&self) -> Vec<Duration> {
+            self.log.lock().unwrap().iter().map(|(_, t)| *t).collect()
+        }
+    }
+
+    #[async_trait]
+    impl Transport for Scripted {
+        async fn execute(
+            &self,
+            _: &Client,
+            request: reqwest::Request,
+        ) -> Result<Response, ClientError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push((request.url().to_string(), self.clock.elapsed()));
+            let (status, headers, body) = self
+                .replies
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| ClientError::Transport("script exhausted".to_string()))?;
+            let mut response = http::Response::builder().status(status);
+            for (name, value) in headers {
+                response = response.header(name, value);
+            }
+            Ok(Response::from(response.body(body).unwrap()))
+        }
+    }
+
+    fn reply(status: u16, body: &str) -> Reply {
+        (status, Vec::new(), body.to_string())
+    }
+
+    fn retry_after(status: u16, seconds: u64) -> Reply {
+        (
+            status,
+            vec![("retry-after", seconds.to_string())],
+            String::new(),
+        )
+    }
+
+    /// A client on `clock` answering from `transport`
+    fn client(clock: &Arc<MockClock>, transport: &Arc<Scripted>) -> RESTClient {
+        RESTClient::new("http://api.test")
+            .with_clock(clock.clone())
+            .with_transport(transport.clone())
+    }
+
+    const EXPONENTIAL: Backoff = Backoff::Exponential {
+        base: Duration::from_secs(1),
+        max: Duration::from_secs(4),
+        factor: 2.0,
+    };
+
+    #[tokio::test]
+    async fn retry_after_is_capped_at_the_backoff_max() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![
+                retry_after(429, 3600),
+                reply(503, ""),
+                retry_after(429, 1),
+                reply(200, "{}"),
+            ],
+        );
+        let client = client(&clock, &transport)
+            .with_retries(3, Duration::ZERO)
+            .with_backoff(EXPONENTIAL);
+
+        let _: Value = client.get("items/").await.unwrap();
+        // 3600 s capped to 4 s, then the 2 s backoff, then the 1 s asked for
+        assert_eq!(
+            transport.times(),
+            [0, 4, 6, 7].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_after_cap_is_configurable() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![
+                retry_after(429, 3600),
+                retry_after(503, 30),
+                reply(200, "{}"),
+            ],
+        );
+        let client = client(&clock, &transport)
+            .with_retries(2, Duration::ZERO)
+            .with_backoff(EXPONENTIAL)
+            .with_max_retry_after(Duration::from_secs(60));
+
+        let _: Value = client.get("items/").await.unwrap();
+        assert_eq!(
+            transport.times(),
+            [0, 60, 90].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn encode_query_follows_the_encoding() {
+        let params = HashMap::from([
+            ("q".to_string(), "a b+c&d=e".to_string()),
+            ("limit".to_string(), "5".to_string()),
+        ]);
+        assert_eq!(
+            encode_query(&params, QueryEncoding::Percent),
+            "limit=5&q=a%20b%2Bc%26d%3De"
+        );
+        assert_eq!(
+            encode_query(&params, QueryEncoding::Form),
+            "limit=5&q=a+b%2Bc%26d%3De"
+        );
+    }
+
+    #[test]
+    fn client_uses_its_schema_managers_encoding() {
+        assert_eq!(
+            RESTClient::new("http://example.com").query_encoding(),
+            QueryEncoding::Percent
+        );
+        let mut schemas = SchemaManager::new();
+        schemas.set_query_encoding(QueryEncoding::Form);
+        let client = RESTClient::with_schemas("http://example.com", schemas);
+        assert_eq!(client.query_encoding(), QueryEncoding::Form);
+    }
+
+    #[tokio::test]
+    async fn max_items_replaces_the_callers_limit() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![reply(
+                200,
+                r#"{"count": 1, "next": null, "previous": null, "results": [{"id": 1}]}"#,
+            )],
+        );
+        let options = GetAllOptions {
+            max_items: Some(500),
+            ..Default::default()
+        };
+
+        let _: Vec<Value> = client(&clock, &transport)
+            .get_all_with_options("items/?limit=5&search=a&limit=7", &options)
+            .await
+            .unwrap();
+        let log = transport.log.lock().unwrap();
+        assert_eq!(log[0].0, "http://api.test/items/?search=a&limit=100");
+    }
+
+    #[tokio::test]
+    async fn zero_max_limit_pages_one_item_at_a_time() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![reply(
+                200,
+                r#"{"count": 1, "next": null, "previous": null, "results": [{"id": 1}]}"#,
+            )],
+        );
+        let mut schemas = SchemaManager::new();
+        schemas.set_max_limit(0);
+        let client = RESTClient::with_schemas("http://api.test", schemas)
+            .with_clock(clock.clone())
+            .with_transport(transport.clone());
+        let options = GetAllOptions {
+            max_items: Some(5),
+            ..Default::default()
+        };
+
+        let _: Vec<Value> = client
+            .get_all_with_options("items/", &options)
+            .await
+            .unwrap();
+        let log = transport.log.lock().unwrap();
+        assert_eq!(log[0].0, "http://api.test/items/?limit=1");
+    }
+
+    #[test]
+    fn replace_query_param_adds_or_replaces() {
+        assert_eq!(
+            replace_query_param("http://a/x".to_string(), "limit", "10"),
+            "http://a/x?limit=10"
+        );
+        assert_eq!(
+            replace_query_param("http://a/x?limited=1&limit=5".to_string(), "limit", "10"),
+            "http://a/x?limited=1&limit=10"
+        );
+    }
+
+    /// Transport that answers `{}` once released, signalling when a
+    /// request arrives and counting requests
+    #[derive(Default)]
+    struct Gate {
+        entered: tokio::sync::Notify,
+        release: tokio::sync::Notify,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Transport for Gate {
+        async fn execute(&self, _: &Client, _: reqwest::Request) -> Result<Response, ClientError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.entered.notify_one();
+            self.release.notified().await;
+            Ok(Response::from(http::Response::new("{}".to_string())))
         }
     }
+
+    #[tokio::test]
+    async fn shutdown_refuses_new_requests() {
+        let client = Arc::new(RESTClient::new("http://api.test"));
+        assert!(client.clone().shutdown(Duration::from_secs(1)).await);
+        let error = client.get::<Value>("items/").await.unwrap_err();
+        assert!(matches!(error, ClientError::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_requests_in_flight() {
+        let gate = Arc::new(Gate::default());
+        let client = Arc::new(RESTClient::new("http://api.test").with_transport(gate.clone()));
+        let request = tokio::spawn({
+            let client = client.clone();
+            async move { client.get::<Value>("items/").await }
+        });
+        gate.entered.notified().await;
+
+        let shutdown = tokio::spawn({
+            let client = client.clone();
+            async move { client.shutdown(Duration::from_secs(10)).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!shutdown.is_finished());
+
+        gate.release.notify_one();
+        assert!(shutdown.await.unwrap());
+        assert!(request.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn shutdown_gives_up_after_the_timeout() {
+        let clock = Arc::new(MockClock::new());
+        let gate = Arc::new(Gate::default());
+        let client = Arc::new(
+            RESTClient::new("http://api.test")
+                .with_clock(clock.clone())
+                .with_transport(gate.clone()),
+        );
+        let request = tokio::spawn({
+            let client = client.clone();
+            async move { client.get::<Value>("items/").await }
+        });
+        gate.entered.notified().await;
+
+        assert!(!client.clone().shutdown(Duration::from_secs(5)).await);
+        assert_eq!(clock.elapsed(), Duration::from_secs(5));
+        gate.release.notify_one();
+        assert!(request.await.unwrap().is_ok());
+    }
+
+    /// Transport that sends headers at once but holds the body open until
+    /// released
+    #[derive(Default)]
+    struct SlowBody {
+        responded: tokio::sync::Notify,
+        release: Arc<tokio::sync::Notify>,
+    }
+
+    #[async_trait]
+    impl Transport for SlowBody {
+        async fn execute(&self, _: &Client, _: reqwest::Request) -> Result<Response, ClientError> {
+            let release = self.release.clone();
+            let body = futures::stream::once(async { Ok::<_, std::io::Error>(Bytes::from("{")) })
+                .chain(futures::stream::once(async move {
+                    release.notified().await;
+                    Ok(Bytes::from("}"))
+                }));
+            self.responded.notify_one();
+            Ok(Response::from(http::Response::new(
+                reqwest::Body::wrap_stream(body),
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn shutdown_waits_for_bodies_still_being_read() {
+        let transport = Arc::new(SlowBody::default());
+        let client = Arc::new(RESTClient::new("http://api.test").with_transport(transport.clone()));
+        let request = tokio::spawn({
+            let client = client.clone();
+            async move { client.get::<Value>("items/").await }
+        });
+        transport.responded.notified().await;
+
+        let shutdown = tokio::spawn(client.shutdown(Duration::from_secs(10)));
+        for _ in 0..20 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!shutdown.is_finished());
+
+        transport.release.notify_one();
+        assert!(shutdown.await.unwrap());
+        assert_eq!(request.await.unwrap().unwrap(), serde_json::json!({}));
+    }
+
+    const PAGE_WITH_NEXT: &str = r#"{"count": 2, "next": "http://api.test/items/?offset=1", "previous": null, "results": [{"id": 1}]}"#;
+
+    #[tokio::test]
+    async fn get_all_before_keeps_time_on_the_clients_clock() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![
+                reply(200, PAGE_WITH_NEXT),
+                reply(503, ""),
+                reply(503, ""),
+                reply(503, ""),
+                reply(503, ""),
+            ],
+        );
+        let client = client(&clock, &transport)
+            .with_retries(0, Duration::ZERO)
+            .with_backoff(EXPONENTIAL);
+
+        let deadline = clock.now() + Duration::from_secs(10);
+        let partial: Partial<Value> = client
+            .get_all_before("items/", &HashMap::new(), deadline)
+            .await
+            .unwrap();
+        assert!(partial.cut_short);
+        assert_eq!(partial.items, vec![serde_json::json!({"id": 1})]);
+        // Retries after 1, 2 and 4 s; the next 4 s delay would pass 10 s
+        assert_eq!(
+            transport.times(),
+            [0, 0, 1, 3, 7].map(Duration::from_secs).to_vec()
+        );
+        assert_eq!(clock.elapsed(), Duration::from_secs(7));
+    }
+
+    #[tokio::test]
+    async fn get_all_before_sends_nothing_past_the_deadline() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(clock.clone(), vec![reply(200, PAGE_WITH_NEXT)]);
+        let client = client(&clock, &transport);
+
+        let deadline = clock.now();
+        clock.advance(Duration::from_secs(1));
+        let partial: Partial<Value> = client
+            .get_all_before("items/", &HashMap::new(), deadline)
+            .await
+            .unwrap();
+        assert!(partial.cut_short);
+        assert!(partial.items.is_empty());
+        assert!(transport.times().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_gets_share_one_request() {
+        const CALLERS: usize = 8;
+        let gate = Arc::new(Gate::default());
+        let client = Arc::new(RESTClient::new("http://api.test").with_transport(gate.clone()));
+        let requests: Vec<_> = (0..CALLERS)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get::<Value>("items/1/").await })
+            })
+            .collect();
+        gate.entered.notified().await;
+
+        // Release only once every other caller is waiting on the first
+        let url = client.build_url("items/1/");
+        while client.in_flight.lock().unwrap()[&url].receiver_count() < CALLERS - 1 {
+            tokio::task::yield_now().await;
+        }
+        gate.release.notify_one();
+
+        for request in requests {
+            assert_eq!(request.await.unwrap().unwrap(), serde_json::json!({}));
+        }
+        assert_eq!(gate.calls.load(Ordering::SeqCst), 1);
+        assert!(client.in_flight.lock().unwrap().is_empty());
+    }
+
+    /// Observer keeping every request event
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<RequestEvent>>);
+
+    impl RequestObserver for Recorder {
+        fn on_request(&self, event: &RequestEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    fn partial(content_range: &str, body: &str) -> Reply {
+        (
+            206,
+            vec![("content-range", content_range.to_string())],
+            body.to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn get_range_checks_the_content_range_start() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![
+                partial("bytes 100-103/200", "abcd"),
+                partial("bytes 0-3/200", "abcd"),
+                reply(206, "abcd"),
+                reply(200, "whole body"),
+            ],
+        );
+        let client = client(&clock, &transport);
+
+        let ranged = client.get_range("file", 100, Some(103)).await.unwrap();
+        assert!(matches!(ranged, Ranged::Partial(body) if body == "abcd"));
+
+        let error = client.get_range("file", 100, None).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ClientError::RangeMismatch { start: 100, content_range: Some(ref range) }
+                if range == "bytes 0-3/200"
+        ));
+
+        let error = client.get_range("file", 100, None).await.unwrap_err();
+        assert!(matches!(
+            error,
+            ClientError::RangeMismatch {
+                start: 100,
+                content_range: None
+            }
+        ));
+
+        let ranged = client.get_range("file", 100, None).await.unwrap();
+        assert!(matches!(ranged, Ranged::Full(body) if body == "whole body"));
+    }
+
+    #[tokio::test]
+    async fn get_range_rejects_an_end_before_the_start() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(clock.clone(), Vec::new());
+        let client = client(&clock, &transport);
+
+        let error = client.get_range("file", 10, Some(9)).await.unwrap_err();
+        assert!(matches!(error, ClientError::InvalidQuery(_)));
+        assert!(transport.times().is_empty());
+        // A single byte is a valid range
+        assert!(matches!(
+            client.get_range("file", 10, Some(10)).await.unwrap_err(),
+            ClientError::Transport(_)
+        ));
+    }
+
+    #[test]
+    fn content_range_start_reads_the_first_byte() {
+        assert_eq!(content_range_start("bytes 100-199/1000"), Some(100));
+        assert_eq!(content_range_start("bytes 0-0/*"), Some(0));
+        assert_eq!(content_range_start("bytes */1000"), None);
+        assert_eq!(content_range_start("items 1-2/3"), None);
+    }
+
+    #[tokio::test]
+    async fn raw_fetches_report_status_and_size() {
+        let clock = Arc::new(MockClock::new());
+        let transport = Scripted::new(
+            clock.clone(),
+            vec![
+                (
+                    200,
+                    vec![("content-type", "text/plain".to_string())],
+                    "hello".to_string(),
+                ),
+                partial("bytes 2-4/5", "llo"),
+                reply(404, r#"{"detail": "Not found."}"#),
+            ],
+        );
+        let recorder = Arc::new(Recorder::default());
+        let client = client(&clock, &transport).with_observer(recorder.clone());
+
+        let (body, content_type) = client.get_bytes("file").await.unwrap();
+        assert_eq!(body, "hello");
+        assert_eq!(content_type.as_deref(), Some("text/plain"));
+        client.get_range("file", 2, None).await.unwrap();
+        assert!(client.get_bytes("missing").await.is_err());
+
+        let events: Vec<_> = recorder
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| (event.status, event.bytes, event.success))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                (Some(200), 5, true),
+                (Some(206), 3, true),
+                (Some(404), 0, false)
+            ]
+        );
+    }
 }
@@ -0,0 +1,323 @@
+//! Retry policy for failed requests
+//!
+//! By default the client never retries. Enable retries with
+//! [`RESTClient::with_retries`](crate::RESTClient::with_retries) and,
+//! optionally, replace the predicate deciding which failures are transient
+//! with [`RESTClient::with_retry_if`](crate::RESTClient::with_retry_if).
+//! The wait between attempts follows a [`Backoff`] strategy, unless the
+//! response said how long to wait in `Retry-After`, capped at the policy's
+//! `max_retry_after`.
+
+use crate::error::ClientError;
+use rand::Rng;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// What the retry predicate knows about a failed attempt
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseInfo<'a> {
+    /// HTTP status, or `None` when no response was received
+    pub status: Option<u16>,
+    /// Error detail parsed from the response body, if any
+    pub detail: Option<&'a str>,
+    /// Number of the attempt that just failed, starting at 1
+    pub attempt: u32,
+}
+
+impl<'a> ResponseInfo<'a> {
+    /// Describe a failed attempt
+    pub fn from_error(error: &'a ClientError, attempt: u32) -> Self {
+        match error {
+            ClientError::Api { status, detail, .. } => Self {
+                status: Some(*status),
+                detail: Some(detail),
+                attempt,
+            },
+            _ => Self {
+                status: None,
+                detail: None,
+                attempt,
+            },
+        }
+    }
+}
+
+/// Decides whether a failed attempt should be retried
+pub type RetryPredicate = Arc<dyn Fn(&ResponseInfo) -> bool + Send + Sync>;
+
+/// Retry transport failures, `429 Too Many Requests` and 5xx responses
+pub fn default_retry_predicate(info: &ResponseInfo) -> bool {
+    match info.status {
+        Some(status) => status == 429 || status >= 500,
+        None => true,
+    }
+}
+
+/// First retry delay of the default backoff, and the delay the executor
+/// uses when none is configured
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// How long to wait before retrying a failed attempt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// The same delay before every retry
+    Fixed(Duration),
+    /// `base`, then multiplied by `factor` for each further retry, capped
+    /// at `max`
+    Exponential {
+        base: Duration,
+        max: Duration,
+        factor: f64,
+    },
+    /// As `Exponential`, but each delay is drawn at random between half
+    /// and all of the exponential delay, so clients that failed together
+    /// do not retry together
+    ExponentialJitter {
+        base: Duration,
+        max: Duration,
+        factor: f64,
+    },
+}
+
+impl Backoff {
+    /// Delay before retrying after attempt number `attempt` (starting at
+    /// 1) failed
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.delay_with(attempt, &mut rand::rng())
+    }
+
+    /// The longest delay this backoff waits
+    pub fn max_delay(&self) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { max, .. } | Backoff::ExponentialJitter { max, .. } => max,
+        }
+    }
+
+    /// As `delay`, drawing any jitter from `rng`
+    pub fn delay_with(&self, attempt: u32, rng: &mut impl Rng) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { base, max, factor } => {
+                exponential_delay(base, max, factor, attempt)
+            }
+            Backoff::ExponentialJitter { base, max, factor } => {
+                let full = exponential_delay(base, max, factor, attempt);
+                let half = full / 2;
+                half + full.saturating_sub(half).mul_f64(rng.random::<f64>())
+            }
+        }
+    }
+}
+
+impl Default for Backoff {
+    /// Exponential with jitter from 500 ms, doubling, up to 30 s
+    fn default() -> Self {
+        Backoff::ExponentialJitter {
+            base: DEFAULT_RETRY_DELAY,
+            max: Duration::from_secs(30),
+            factor: 2.0,
+        }
+    }
+}
+
+/// `base * factor^(attempt - 1)`, capped at `max`
+fn exponential_delay(base: Duration, max: Duration, factor: f64, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+    let secs = base.as_secs_f64() * factor.powi(exponent);
+    Duration::try_from_secs_f64(secs).map_or(max, |delay| delay.min(max))
+}
+
+/// How many times, how often and on what to retry a request
+#[derive(Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    /// Wait between attempts when the response gives no `Retry-After`
+    pub backoff: Backoff,
+    /// Decides which failures are retried
+    pub predicate: RetryPredicate,
+    /// Longest `Retry-After` honoured; longer waits are cut to this.
+    /// `None` uses the backoff's `max_delay`
+    pub max_retry_after: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// `retry_after` capped at `max_retry_after`
+    pub fn clamp_retry_after(&self, retry_after: Duration) -> Duration {
+        retry_after.min(
+            self.max_retry_after
+                .unwrap_or_else(|| self.backoff.max_delay()),
+        )
+    }
+
+    /// Whether the failure of `attempt` should be retried
+    pub fn should_retry(&self, error: &ClientError, attempt: u32) -> bool {
+        attempt <= self.max_retries && (self.predicate)(&ResponseInfo::from_error(error, attempt))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Backoff::default(),
+            predicate: Arc::new(default_retry_predicate),
+            max_retry_after: None,
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("backoff", &self.backoff)
+            .field("max_retry_after", &self.max_retry_after)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Retries shared by every request of one batch, such as a `get_all` walk
+///
+/// Each retry any request makes takes one from the budget; once it is
+/// spent, failures are returned instead of retried.
+#[derive(Debug)]
+pub(crate) struct RetryBudget {
+    remaining: AtomicU32,
+}
+
+impl RetryBudget {
+    /// Allow `retries` retries in total
+    pub(crate) fn new(retries: u32) -> Self {
+        Self {
+            remaining: AtomicU32::new(retries),
+        }
+    }
+
+    /// Take one retry, or return `false` if none are left
+    pub(crate) fn try_take(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RESTClient;
+    use crate::client::clock::MockClock;
+    use crate::client::transport::Transport;
+    use async_trait::async_trait;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use reqwest::{Client, Response};
+    use std::sync::Mutex;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn sequence(backoff: Backoff, attempts: u32) -> Vec<Duration> {
+        (1..=attempts)
+            .map(|attempt| backoff.delay(attempt))
+            .collect()
+    }
+
+    #[test]
+    fn fixed_waits_the_same_every_time() {
+        assert_eq!(sequence(Backoff::Fixed(ms(250)), 4), [ms(250); 4]);
+    }
+
+    #[test]
+    fn exponential_grows_by_factor_up_to_max() {
+        let backoff = Backoff::Exponential {
+            base: ms(100),
+            max: ms(1000),
+            factor: 3.0,
+        };
+        assert_eq!(
+            sequence(backoff, 5),
+            [ms(100), ms(300), ms(900), ms(1000), ms(1000)]
+        );
+        // Huge attempt numbers saturate instead of overflowing
+        assert_eq!(backoff.delay(u32::MAX), ms(1000));
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_all_of_the_exponential_delay() {
+        let jitter = Backoff::ExponentialJitter {
+            base: ms(100),
+            max: ms(1000),
+            factor: 2.0,
+        };
+        let plain = Backoff::Exponential {
+            base: ms(100),
+            max: ms(1000),
+            factor: 2.0,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        for attempt in 1..=8 {
+            let full = plain.delay(attempt);
+            for _ in 0..50 {
+                let delay = jitter.delay_with(attempt, &mut rng);
+                assert!(
+                    full / 2 <= delay && delay <= full,
+                    "attempt {attempt}: {delay:?} outside {:?}..={full:?}",
+                    full / 2
+                );
+            }
+        }
+        // The same seed draws the same delays
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (1..=4)
+                .map(|attempt| jitter.delay_with(attempt, &mut rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draw(7), draw(7));
+    }
+
+    /// Transport answering `429` without `Retry-After` `failures` times,
+    /// then `{}`
+    struct TooManyRequests {
+        failures: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl Transport for TooManyRequests {
+        async fn execute(&self, _: &Client, _: reqwest::Request) -> Result<Response, ClientError> {
+            let mut failures = self.failures.lock().unwrap();
+            let response = if *failures > 0 {
+                *failures -= 1;
+                http::Response::builder().status(429).body(String::new())
+            } else {
+                http::Response::builder().body("{}".to_string())
+            };
+            Ok(Response::from(response.unwrap()))
+        }
+    }
+
+    #[tokio::test]
+    async fn client_sleeps_the_backoff_sequence_on_its_clock() {
+        let clock = Arc::new(MockClock::new());
+        let client = RESTClient::new("http://api.test")
+            .with_clock(clock.clone())
+            .with_transport(Arc::new(TooManyRequests {
+                failures: Mutex::new(4),
+            }))
+            .with_retries(4, Duration::ZERO)
+            .with_backoff(Backoff::Exponential {
+                base: ms(100),
+                max: ms(500),
+                factor: 2.0,
+            });
+
+        let _: serde_json::Value = client.get("items/").await.unwrap();
+        // 100 + 200 + 400 + 500 ms
+        assert_eq!(clock.elapsed(), ms(1200));
+    }
+}
@@ -0,0 +1,190 @@
+//! JSON filter bodies for POST-based search
+//!
+//! Some gateways in front of the Spaceflight News API accept searches as a
+//! POST with a JSON body instead of query parameters, which allows filters
+//! the query string cannot express, such as `OR` across fields. Build the
+//! body with [`SearchFilter`] and send it with
+//! [`SpaceDevsClient::search_post`](crate::SpaceDevsClient::search_post).
+//!
+//! A filter serializes as nested objects:
+//!
+//! ```json
+//! {
+//!   "filter": {"and": [
+//!     {"field": "news_site", "op": "in", "value": ["NASA", "ESA"]},
+//!     {"or": [
+//!       {"field": "title", "op": "contains", "value": "Artemis"},
+//!       {"field": "summary", "op": "contains", "value": "Artemis"}
+//!     ]}
+//!   ]},
+//!   "limit": 20
+//! }
+//! ```
+
+use crate::client::query::format_timestamp;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Comparison applied by a [`Filter::Condition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterOp {
+    /// Field equals the value
+    Eq,
+    /// Field equals one of the values in an array
+    In,
+    /// Field contains the value as a case-insensitive substring
+    Contains,
+    /// Field is greater than or equal to the value
+    Gte,
+    /// Field is less than or equal to the value
+    Lte,
+}
+
+/// A condition tree over content fields
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Filter {
+    /// Every filter must match
+    And(Vec<Filter>),
+    /// At least one filter must match
+    Or(Vec<Filter>),
+    /// The filter must not match
+    Not(Box<Filter>),
+    /// Compare one field against a value
+    #[serde(untagged)]
+    Condition {
+        field: String,
+        op: FilterOp,
+        value: Value,
+    },
+}
+
+impl Filter {
+    /// Compare `field` against `value` with `op`
+    pub fn condition(field: impl Into<String>, op: FilterOp, value: impl Into<Value>) -> Self {
+        Filter::Condition {
+            field: field.into(),
+            op,
+            value: value.into(),
+        }
+    }
+
+    /// `field` equals `value`
+    pub fn eq(field: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self::condition(field, FilterOp::Eq, value)
+    }
+
+    /// `field` equals any of `values`
+    pub fn one_of<I, V>(field: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        let values: Vec<Value> = values.into_iter().map(Into::into).collect();
+        Self::condition(field, FilterOp::In, values)
+    }
+
+    /// `field` contains `text`, ignoring case
+    pub fn contains(field: impl Into<String>, text: impl Into<String>) -> Self {
+        Self::condition(field, FilterOp::Contains, text.into())
+    }
+
+    /// `field` is a timestamp between `start` and `end`, inclusive
+    pub fn between(field: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        let field = field.into();
+        Filter::And(vec![
+            Self::condition(field.clone(), FilterOp::Gte, format_timestamp(start)),
+            Self::condition(field, FilterOp::Lte, format_timestamp(end)),
+        ])
+    }
+
+    /// Published by any of `sites`
+    pub fn news_sites<I, S>(sites: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::one_of("news_site", sites.into_iter().map(Into::into))
+    }
+
+    /// Published between `start` and `end`, inclusive
+    pub fn published_between(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self::between("published_at", start, end)
+    }
+
+    /// Every one of `filters` must match
+    pub fn and(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::And(filters.into_iter().collect())
+    }
+
+    /// At least one of `filters` must match
+    pub fn or(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::Or(filters.into_iter().collect())
+    }
+}
+
+/// `!filter` matches where `filter` doesn't
+impl std::ops::Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+}
+
+/// Body of a POST search: a filter plus paging and ordering
+///
+/// Filters added with [`filter`](Self::filter) must all match; combine
+/// them with [`Filter::or`] for alternatives. An empty search matches
+/// everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ordering: Option<String>,
+}
+
+impl SearchFilter {
+    /// Create a search matching everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `filter` to match as well
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(match self.filter.take() {
+            None => filter,
+            Some(Filter::And(mut filters)) => {
+                filters.push(filter);
+                Filter::And(filters)
+            }
+            Some(existing) => Filter::And(vec![existing, filter]),
+        });
+        self
+    }
+
+    /// Number of results per page
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Number of results to skip
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sort order, e.g. `-published_at`
+    pub fn ordering(mut self, ordering: impl Into<String>) -> Self {
+        self.ordering = Some(ordering.into());
+        self
+    }
+}
@@ -0,0 +1,667 @@
+//! Typed client for the Spaceflight News API
+//!
+//! Where [`RESTClient`] works with any endpoint and raw JSON,
+//! [`SpaceDevsClient`] knows the Spaceflight News API's endpoints and
+//! returns the typed content structs. The content fetches shared by both
+//! clients are also available through [`SpaceflightApi`](crate::SpaceflightApi).
+
+use crate::client::query::{ContentQuery, LaunchQuery, format_timestamp};
+use crate::client::rest_client::{GetAllOptions, RESTClient, encode_query};
+use crate::client::search::SearchFilter;
+use crate::endpoints::{
+    article::Article,
+    blog::Blog,
+    content::ContentItem,
+    event::EventDetail,
+    info::{ApiVersion, Info},
+    launch::LaunchDetail,
+    paginated::Paginated,
+    report::Report,
+};
+use crate::error::ClientError;
+use crate::schema::{DEFAULT_MAX_LIMIT, SchemaManager};
+use crate::utils::urls::{SPACEDEVS_DATA_API_BASE, SPACEFLIGHT_NEWS_API_BASE};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Per-site count requests `news_site_counts` keeps in flight
+pub const NEWS_SITE_COUNT_CONCURRENCY: usize = 4;
+
+/// Typed client for the Spaceflight News API
+pub struct SpaceDevsClient {
+    /// Underlying REST client
+    rest: RESTClient,
+    /// REST client for Launch Library 2, used for launch and event details
+    data: RESTClient,
+    /// Source of randomness for `get_random_article`
+    rng: Mutex<StdRng>,
+    /// API version found by `detect_version`
+    version: Mutex<Option<ApiVersion>>,
+}
+
+impl SpaceDevsClient {
+    /// Create a client for the public Spaceflight News API
+    pub fn new() -> Self {
+        Self::with_base_url(SPACEFLIGHT_NEWS_API_BASE)
+    }
+
+    /// Create a client for a Spaceflight News API deployment at `base_url`
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self::from_rest_client(RESTClient::new(base_url))
+    }
+
+    /// Create a client for the public Spaceflight News API that applies
+    /// schema defaults
+    ///
+    /// List fetches merge in the default query parameters of the
+    /// `articles`, `blogs` and `reports` schemas; parameters passed by the
+    /// caller take precedence. Endpoints without a schema of that name are
+    /// fetched as given.
+    pub fn with_schemas(schema_manager: SchemaManager) -> Self {
+        Self::from_rest_client(RESTClient::with_schemas(
+            SPACEFLIGHT_NEWS_API_BASE,
+            schema_manager,
+        ))
+    }
+
+    /// Create a client on top of a configured `RESTClient`
+    ///
+    /// If `rest` has a schema manager, its defaults are applied as
+    /// described in `with_schemas`.
+    pub fn from_rest_client(rest: RESTClient) -> Self {
+        Self {
+            rest,
+            data: RESTClient::new(SPACEDEVS_DATA_API_BASE),
+            rng: Mutex::new(StdRng::from_os_rng()),
+            version: Mutex::new(None),
+        }
+    }
+
+    /// Seed the random number generator so random picks are reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    /// Use `data` for Launch Library 2 requests instead of a default
+    /// client for the public LL2 API
+    pub fn with_data_rest_client(mut self, data: RESTClient) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Get the underlying REST client
+    pub fn rest(&self) -> &RESTClient {
+        &self.rest
+    }
+
+    /// Merge a schema's default query parameters under `params`
+    fn with_defaults<'a>(
+        &self,
+        schema_name: &str,
+        params: &'a HashMap<String, String>,
+    ) -> Result<Cow<'a, HashMap<String, String>>, ClientError> {
+        let Some(schema_manager) = self.rest.schema_manager() else {
+            return Ok(Cow::Borrowed(params));
+        };
+        if schema_manager.get_schema(schema_name).is_none() {
+            return Ok(Cow::Borrowed(params));
+        }
+
+        let mut merged = schema_manager.default_params(schema_name)?;
+        merged.extend(params.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(Cow::Owned(merged))
+    }
+
+    /// Fetch the items of a content endpoint matching `query`, with the
+    /// schema's defaults, from one page or all of them
+    async fn fetch_content<T>(
+        &self,
+        endpoint: &str,
+        query: &ContentQuery,
+        all_pages: bool,
+    ) -> Result<Vec<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let params = query.params();
+        let params = self.with_defaults(endpoint, &params)?;
+        if all_pages {
+            let query = encode_query(&params, self.rest.query_encoding());
+            let endpoint = if query.is_empty() {
+                format!("{}/", endpoint)
+            } else {
+                format!("{}/?{}", endpoint, query)
+            };
+            self.rest.get_all(&endpoint).await
+        } else {
+            let page: Paginated<T> = self
+                .rest
+                .get_with_query(&format!("{}/", endpoint), &params)
+                .await?;
+            Ok(page.results)
+        }
+    }
+
+    /// Fetch a page of articles
+    pub async fn get_articles(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Article>, ClientError> {
+        let params = self.with_defaults("articles", params)?;
+        self.rest.get_with_query("articles/", &params).await
+    }
+
+    /// Fetch a page of articles matching `query`
+    pub async fn get_articles_with(
+        &self,
+        query: &ContentQuery,
+    ) -> Result<Paginated<Article>, ClientError> {
+        self.get_articles(&query.params()).await
+    }
+
+    /// Fetch articles matching `query` keyed by ID
+    ///
+    /// Fetches one page, or every page from the query's offset on when
+    /// `all_pages` is set. If an ID comes back more than once, as when
+    /// items shift between pages, the last occurrence wins.
+    pub async fn get_articles_map(
+        &self,
+        query: &ContentQuery,
+        all_pages: bool,
+    ) -> Result<HashMap<u32, Article>, ClientError> {
+        let articles: Vec<Article> = self.fetch_content("articles", query, all_pages).await?;
+        Ok(articles
+            .into_iter()
+            .map(|article| (article.id, article))
+            .collect())
+    }
+
+    /// Fetch a single article by ID
+    pub async fn get_article(&self, id: u32) -> Result<Article, ClientError> {
+        self.rest.get(&format!("articles/{}/", id)).await
+    }
+
+    /// Fetch every article created or modified at or after `since`
+    ///
+    /// Filters on `updated_at__gte`, which the API supports alongside
+    /// `published_at__gte`; `updated_at` also moves when an existing article
+    /// is edited, so this catches corrections as well as new articles.
+    /// Results are ordered by `updated_at` and all pages are followed.
+    pub async fn get_articles_updated_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Article>, ClientError> {
+        let endpoint = format!(
+            "articles/?updated_at__gte={}&ordering=updated_at&limit={}",
+            urlencoding::encode(&format_timestamp(since)),
+            DEFAULT_MAX_LIMIT
+        );
+        self.rest.get_all(&endpoint).await
+    }
+
+    /// Fetch every article by the author called `name`
+    ///
+    /// Sends the API's `author` filter so the server narrows the results,
+    /// then keeps only articles listing an author whose name matches
+    /// `name` case-insensitively (ignoring surrounding whitespace). The
+    /// client-side check makes the result exact either way, but a mirror
+    /// that ignores the filter costs a walk over every article. Returns an
+    /// empty list if the author has no articles.
+    pub async fn get_articles_by_author(&self, name: &str) -> Result<Vec<Article>, ClientError> {
+        let wanted = name.trim().to_lowercase();
+        let endpoint = format!(
+            "articles/?author={}&limit={}",
+            urlencoding::encode(name.trim()),
+            DEFAULT_MAX_LIMIT
+        );
+        let articles: Vec<Article> = self.rest.get_all(&endpoint).await?;
+        Ok(articles
+            .into_iter()
+            .filter(|article| {
+                article
+                    .authors
+                    .iter()
+                    .any(|author| author.name.trim().to_lowercase() == wanted)
+            })
+            .collect())
+    }
+
+    /// Search articles by POSTing `body` to `articles/search/`
+    ///
+    /// For gateways that accept JSON filters too complex for query
+    /// parameters; the public API has no such endpoint. Returns one page,
+    /// as set by the filter's `limit` and `offset`.
+    pub async fn search_post(&self, body: SearchFilter) -> Result<Paginated<Article>, ClientError> {
+        self.rest.post("articles/search/", &body).await
+    }
+
+    /// Fetch a page of blogs
+    pub async fn get_blogs(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Blog>, ClientError> {
+        let params = self.with_defaults("blogs", params)?;
+        self.rest.get_with_query("blogs/", &params).await
+    }
+
+    /// Fetch a page of blogs matching `query`
+    pub async fn get_blogs_with(
+        &self,
+        query: &ContentQuery,
+    ) -> Result<Paginated<Blog>, ClientError> {
+        self.get_blogs(&query.params()).await
+    }
+
+    /// Fetch blogs matching `query` keyed by ID
+    ///
+    /// Pages and duplicate IDs are handled as in
+    /// [`get_articles_map`](Self::get_articles_map).
+    pub async fn get_blogs_map(
+        &self,
+        query: &ContentQuery,
+        all_pages: bool,
+    ) -> Result<HashMap<u32, Blog>, ClientError> {
+        let blogs: Vec<Blog> = self.fetch_content("blogs", query, all_pages).await?;
+        Ok(blogs.into_iter().map(|blog| (blog.id, blog)).collect())
+    }
+
+    /// Fetch a single blog by ID
+    pub async fn get_blog(&self, id: u32) -> Result<Blog, ClientError> {
+        self.rest.get(&format!("blogs/{}/", id)).await
+    }
+
+    /// Fetch a page of reports
+    pub async fn get_reports(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<Paginated<Report>, ClientError> {
+        let params = self.with_defaults("reports", params)?;
+        self.rest.get_with_query("reports/", &params).await
+    }
+
+    /// Fetch a page of reports matching `query`
+    pub async fn get_reports_with(
+        &self,
+        query: &ContentQuery,
+    ) -> Result<Paginated<Report>, ClientError> {
+        self.get_reports(&query.params()).await
+    }
+
+    /// Fetch reports matching `query` keyed by ID
+    ///
+    /// Pages and duplicate IDs are handled as in
+    /// [`get_articles_map`](Self::get_articles_map).
+    pub async fn get_reports_map(
+        &self,
+        query: &ContentQuery,
+        all_pages: bool,
+    ) -> Result<HashMap<u32, Report>, ClientError> {
+        let reports: Vec<Report> = self.fetch_content("reports", query, all_pages).await?;
+        Ok(reports
+            .into_iter()
+            .map(|report| (report.id, report))
+            .collect())
+    }
+
+    /// Fetch a single report by ID
+    pub async fn get_report(&self, id: u32) -> Result<Report, ClientError> {
+        self.rest.get(&format!("reports/{}/", id)).await
+    }
+
+    /// Fetch the full Launch Library 2 record of a launch by its UUID
+    pub async fn get_launch_detail(&self, id: &str) -> Result<LaunchDetail, ClientError> {
+        self.data.get(&format!("launches/{}/", id)).await
+    }
+
+    /// Fetch a page of upcoming launches from Launch Library 2, soonest
+    /// first
+    pub async fn upcoming_launches(
+        &self,
+        query: &LaunchQuery,
+    ) -> Result<Paginated<LaunchDetail>, ClientError> {
+        self.data
+            .get_with_query("launches/upcoming/", &query.params())
+            .await
+    }
+
+    /// Fetch a page of past launches from Launch Library 2, most recent
+    /// first
+    pub async fn previous_launches(
+        &self,
+        query: &LaunchQuery,
+    ) -> Result<Paginated<LaunchDetail>, ClientError> {
+        self.data
+            .get_with_query("launches/previous/", &query.params())
+            .await
+    }
+
+    /// Fetch the full Launch Library 2 record of an event by its ID
+    pub async fn get_event_detail(&self, id: u32) -> Result<EventDetail, ClientError> {
+        self.data.get(&format!("events/{}/", id)).await
+    }
+
+    /// Fetch the API version and the news sites it aggregates
+    pub async fn info(&self) -> Result<Info, ClientError> {
+        self.rest.get("info/").await
+    }
+
+    /// Read the API version from `info/` and remember it
+    ///
+    /// The content structs decode both v3 and v4 field names (see
+    /// [`ApiVersion`]), so this is not needed to parse responses; it lets
+    /// callers check which version a mirror serves. Returns
+    /// `ClientError::UnsupportedVersion` for versions before 3 or strings
+    /// that do not start with a major version number.
+    pub async fn detect_version(&self) -> Result<ApiVersion, ClientError> {
+        let info = self.info().await?;
+        let version = info
+            .api_version()
+            .ok_or(ClientError::UnsupportedVersion(info.version))?;
+        *self.version.lock().unwrap() = Some(version);
+        Ok(version)
+    }
+
+    /// API version found by the last successful `detect_version`, if any
+    pub fn api_version(&self) -> Option<ApiVersion> {
+        *self.version.lock().unwrap()
+    }
+
+    /// Fetch the news sites the API aggregates, as listed by `info/`
+    pub async fn news_sites(&self) -> Result<Vec<String>, ClientError> {
+        Ok(self.info().await?.news_sites)
+    }
+
+    /// Number of articles from each news site, most first
+    ///
+    /// Lists the sites from `/info`, then reads each site's `count` with a
+    /// one-item `news_site` query, up to `NEWS_SITE_COUNT_CONCURRENCY` at
+    /// a time. The requests are spaced by the REST client's rate limiter,
+    /// if one is set with `RESTClient::with_rate_limiter`. Sites with the
+    /// same count are ordered by name.
+    pub async fn news_site_counts(&self) -> Result<Vec<(String, u32)>, ClientError> {
+        let sites = self.news_sites().await?;
+        let mut counts: Vec<(String, u32)> = stream::iter(sites)
+            .map(|site| async move {
+                let params = HashMap::from([
+                    ("news_site".to_string(), site.clone()),
+                    ("limit".to_string(), "1".to_string()),
+                ]);
+                let page: Paginated<serde_json::Value> =
+                    self.rest.get_with_query("articles/", &params).await?;
+                Ok::<_, ClientError>((site, page.count))
+            })
+            .buffer_unordered(NEWS_SITE_COUNT_CONCURRENCY)
+            .try_collect()
+            .await?;
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Download an article's image
+    ///
+    /// Returns the image bytes and the `Content-Type` the server sent. The
+    /// request goes through the REST client, so its `reqwest::Client`
+    /// settings, transport, retries, observer and `max_response_bytes`
+    /// apply, but not its auth: images are usually served by the news
+    /// site, not the API. Fails with `ClientError::Image` if the article
+    /// has no `image_url` or the response has no `image/*` content type.
+    pub async fn fetch_image(
+        &self,
+        article: &Article,
+    ) -> Result<(Vec<u8>, Option<String>), ClientError> {
+        let image_url = article.image_url.as_deref().unwrap_or_default().trim();
+        if image_url.is_empty() {
+            return Err(ClientError::Image(format!(
+                "article {} has no image_url",
+                article.id
+            )));
+        }
+
+        let (bytes, content_type) = self.rest.get_external_bytes(image_url).await?;
+        match &content_type {
+            Some(content_type) if content_type.to_ascii_lowercase().starts_with("image/") => {}
+            Some(content_type) => {
+                return Err(ClientError::Image(format!(
+                    "expected an image from {}, got {}",
+                    image_url, content_type
+                )));
+            }
+            None => {
+                return Err(ClientError::Image(format!(
+                    "expected an image from {}, got no Content-Type",
+                    image_url
+                )));
+            }
+        }
+        Ok((bytes.to_vec(), content_type))
+    }
+
+    /// Number of pages of `limit` items a list endpoint currently has
+    ///
+    /// Reads the `count` with a one-item request to `endpoint`, e.g.
+    /// `"articles/"`. Pages can then be fetched independently by `offset`.
+    pub async fn page_count(&self, endpoint: &str, limit: u32) -> Result<u32, ClientError> {
+        let params = HashMap::from([("limit".to_string(), "1".to_string())]);
+        let page: Paginated<serde_json::Value> =
+            self.rest.get_with_query(endpoint, &params).await?;
+        Ok(page.total_pages(limit))
+    }
+
+    /// Fetch a random article
+    ///
+    /// Reads the current `count` with a one-item request, then fetches the
+    /// article at a random offset. Returns `ClientError::Empty` if there are
+    /// no articles.
+    pub async fn get_random_article(&self) -> Result<Article, ClientError> {
+        let empty = || ClientError::Empty {
+            endpoint: "articles/".to_string(),
+        };
+
+        let mut params = HashMap::from([("limit".to_string(), "1".to_string())]);
+        let count = self.get_articles(&params).await?.count;
+        if count == 0 {
+            return Err(empty());
+        }
+
+        let offset = self.rng.lock().unwrap().random_range(0..count);
+        params.insert("offset".to_string(), offset.to_string());
+        self.get_articles(&params)
+            .await?
+            .results
+            .into_iter()
+            .next()
+            .ok_or_else(empty)
+    }
+
+    /// The `n` most recently published items across articles, blogs and reports
+    ///
+    /// Fetches the newest `n` of each type concurrently, then merges them
+    /// newest first by `published_at`. Items whose timestamp doesn't parse
+    /// sort last. Fails if any of the three fetches fails.
+    pub async fn latest_feed(&self, n: usize) -> Result<Vec<ContentItem>, ClientError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let options = GetAllOptions {
+            max_items: Some(n),
+            ..Default::default()
+        };
+        let (articles, blogs, reports) = tokio::try_join!(
+            self.rest
+                .get_all_with_options::<Article>("articles/?ordering=-published_at", &options),
+            self.rest
+                .get_all_with_options::<Blog>("blogs/?ordering=-published_at", &options),
+            self.rest
+                .get_all_with_options::<Report>("reports/?ordering=-published_at", &options),
+        )?;
+
+        let mut items: Vec<ContentItem> = articles
+            .into_iter()
+            .map(ContentItem::Article)
+            .chain(blogs.into_iter().map(ContentItem::Blog))
+            .chain(reports.into_iter().map(ContentItem::Report))
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.published_at_utc()));
+        items.truncate(n);
+        Ok(items)
+    }
+
+    /// Find the article whose `url` is exactly `url`
+    ///
+    /// The API has no exact URL filter, so this runs a full-text `search`
+    /// for the URL and picks the result whose `url` matches, ignoring a
+    /// trailing slash. Returns `None` when nothing matches.
+    pub async fn find_by_url(&self, url: &str) -> Result<Option<Article>, ClientError> {
+        let wanted = url.trim().trim_end_matches('/');
+        let params = HashMap::from([
+            ("search".to_string(), wanted.to_string()),
+            ("limit".to_string(), DEFAULT_MAX_LIMIT.to_string()),
+        ]);
+
+        let page = self.get_articles(&params).await?;
+        Ok(page
+            .results
+            .into_iter()
+            .find(|article| article.url.trim_end_matches('/') == wanted))
+    }
+}
+
+impl Default for SpaceDevsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::auth::Auth;
+    use crate::client::retry::Backoff;
+    use crate::client::transport::Transport;
+    use async_trait::async_trait;
+    use reqwest::{Client, Response};
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// A canned response: status, `Content-Type` and body
+    type Reply = (u16, Option<&'static str>, &'static str);
+
+    /// Transport serving canned responses in order, logging each request's
+    /// URL and whether it carried an `Authorization` header
+    #[derive(Default)]
+    struct Scripted {
+        replies: Mutex<VecDeque<Reply>>,
+        log: Mutex<Vec<(String, bool)>>,
+    }
+
+    #[async_trait]
+    impl Transport for Scripted {
+        async fn execute(
+            &self,
+            _: &Client,
+            request: reqwest::Request,
+        ) -> Result<Response, ClientError> {
+            self.log.lock().unwrap().push((
+                request.url().to_string(),
+                request
+                    .headers()
+                    .contains_key(reqwest::header::AUTHORIZATION),
+            ));
+            let (status, content_type, body) = self.replies.lock().unwrap().pop_front().unwrap();
+            let mut response = http::Response::builder().status(status);
+            if let Some(content_type) = content_type {
+                response = response.header(reqwest::header::CONTENT_TYPE, content_type);
+            }
+            Ok(Response::from(response.body(body).unwrap()))
+        }
+    }
+
+    fn client(replies: Vec<Reply>) -> (SpaceDevsClient, Arc<Scripted>) {
+        let transport = Arc::new(Scripted {
+            replies: Mutex::new(replies.into()),
+            ..Default::default()
+        });
+        let rest = RESTClient::new("http://api.test")
+            .with_transport(transport.clone())
+            .with_auth(Auth::Bearer("secret".to_string()))
+            .with_retries(1, Duration::ZERO)
+            .with_backoff(Backoff::Fixed(Duration::ZERO))
+            .with_max_response_bytes(16);
+        (SpaceDevsClient::from_rest_client(rest), transport)
+    }
+
+    fn article(image_url: Option<&str>) -> Article {
+        serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "title": "Launch",
+            "url": "https://news.test/launch",
+            "image_url": image_url,
+            "news_site": "News",
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T12:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn images_go_through_the_transport_without_auth() {
+        let (client, transport) =
+            client(vec![(503, None, ""), (200, Some("image/png"), "PNGDATA")]);
+        let (bytes, content_type) = client
+            .fetch_image(&article(Some(" https://cdn.test/a.png ")))
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"PNGDATA");
+        assert_eq!(content_type.as_deref(), Some("image/png"));
+        // Retried once, and never sent the API's credentials
+        assert_eq!(
+            *transport.log.lock().unwrap(),
+            [
+                ("https://cdn.test/a.png".to_string(), false),
+                ("https://cdn.test/a.png".to_string(), false)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn non_image_or_untyped_responses_are_rejected() {
+        let (client, _) = client(vec![
+            (200, Some("text/html"), "<html>"),
+            (200, None, "GIF89a"),
+        ]);
+        let image = article(Some("https://cdn.test/a.gif"));
+        for _ in 0..2 {
+            let error = client.fetch_image(&image).await.unwrap_err();
+            assert!(matches!(error, ClientError::Image(_)), "{error}");
+        }
+    }
+
+    #[tokio::test]
+    async fn images_respect_the_size_limit() {
+        let (client, _) = client(vec![(200, Some("image/jpeg"), "0123456789abcdefXYZ")]);
+        let error = client
+            .fetch_image(&article(Some("https://cdn.test/big.jpg")))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ClientError::TooLarge { limit: 16 }));
+    }
+
+    #[tokio::test]
+    async fn missing_image_url_sends_nothing() {
+        let (client, transport) = client(Vec::new());
+        for image_url in [None, Some("  ")] {
+            let error = client.fetch_image(&article(image_url)).await.unwrap_err();
+            assert_eq!(error.to_string(), "Image error: article 7 has no image_url");
+        }
+        assert!(transport.log.lock().unwrap().is_empty());
+    }
+}
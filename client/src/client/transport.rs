@@ -0,0 +1,27 @@
+//! Pluggable transport used by the REST client to execute requests
+//!
+//! The default [`HttpTransport`] sends requests over the network with the
+//! client's `reqwest::Client`. Swapping in another [`Transport`] lets tests
+//! and offline demos serve canned responses instead.
+
+use crate::error::ClientError;
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response};
+
+/// Executes a prepared request and returns its response
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Execute `request`, using `client` if the transport goes to the network
+    async fn execute(&self, client: &Client, request: Request) -> Result<Response, ClientError>;
+}
+
+/// Transport that sends requests over the network
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransport;
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn execute(&self, client: &Client, request: Request) -> Result<Response, ClientError> {
+        Ok(client.execute(request).await?)
+    }
+}
@@ -0,0 +1,325 @@
+//! VCR-style recording and replay of HTTP interactions
+//!
+//! In [`VcrMode::Record`] every request is forwarded to an inner transport
+//! and the request/response pair is written to a JSON cassette file. In
+//! [`VcrMode::Replay`] responses are served from the cassette without any
+//! network access, which keeps tests and demos reproducible.
+//!
+//! Response bodies are stored as text, so cassettes are intended for the
+//! JSON APIs this crate talks to. Query parameters that usually carry
+//! credentials ([`DEFAULT_REDACTED_PARAMS`], plus any added with
+//! [`RecordingTransport::with_redacted_param`]) are stored with their value
+//! replaced, so an [`Auth::ApiKeyQuery`](crate::Auth::ApiKeyQuery) key never
+//! reaches the cassette.
+
+use crate::client::transport::{HttpTransport, Transport};
+use crate::error::ClientError;
+use async_trait::async_trait;
+use reqwest::{Client, Request, Response, Url};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Query parameters whose values are redacted by default
+pub const DEFAULT_REDACTED_PARAMS: &[&str] = &["api_key", "apikey", "key", "token", "access_token"];
+
+/// Value stored in place of a redacted query parameter
+const REDACTED: &str = "REDACTED";
+
+/// Whether a `RecordingTransport` writes or reads its cassette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// Forward requests to the network and save each interaction
+    Record,
+    /// Serve responses from the cassette without network access
+    Replay,
+}
+
+/// A single recorded request/response pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// HTTP method of the request
+    pub method: String,
+    /// Full request URL including the query string, with credential
+    /// parameters redacted
+    pub url: String,
+    /// Response status code
+    pub status: u16,
+    /// Response headers
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: String,
+}
+
+/// Transport that records interactions to, or replays them from, a cassette
+pub struct RecordingTransport {
+    /// Record or replay
+    mode: VcrMode,
+    /// Cassette file location
+    path: PathBuf,
+    /// Transport used to reach the network while recording
+    inner: Arc<dyn Transport>,
+    /// Interactions recorded so far, or loaded for replay
+    interactions: Mutex<Vec<Interaction>>,
+    /// Which loaded interactions have already been replayed
+    replayed: Mutex<Vec<bool>>,
+    /// Query parameters whose values are never stored or matched
+    redacted: Vec<String>,
+}
+
+impl RecordingTransport {
+    /// Record interactions made over the network into the cassette at `path`
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::record_with(path, Arc::new(HttpTransport))
+    }
+
+    /// Record interactions made through `inner` into the cassette at `path`
+    pub fn record_with(path: impl Into<PathBuf>, inner: Arc<dyn Transport>) -> Self {
+        Self {
+            mode: VcrMode::Record,
+            path: path.into(),
+            inner,
+            interactions: Mutex::new(Vec::new()),
+            replayed: Mutex::new(Vec::new()),
+            redacted: Self::default_redacted(),
+        }
+    }
+
+    /// Replay interactions from the cassette at `path`
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            ClientError::Transport(format!("cannot read cassette {}: {}", path.display(), e))
+        })?;
+        let interactions: Vec<Interaction> = serde_json::from_str(&contents).map_err(|e| {
+            ClientError::Transport(format!("invalid cassette {}: {}", path.display(), e))
+        })?;
+
+        Ok(Self {
+            mode: VcrMode::Replay,
+            path,
+            inner: Arc::new(HttpTransport),
+            replayed: Mutex::new(vec![false; interactions.len()]),
+            interactions: Mutex::new(interactions),
+            redacted: Self::default_redacted(),
+        })
+    }
+
+    /// Also redact the query parameter `name`, such as the parameter an
+    /// `Auth::ApiKeyQuery` sends its key in
+    pub fn with_redacted_param(mut self, name: impl Into<String>) -> Self {
+        self.redacted.push(name.into());
+        self
+    }
+
+    /// Get the mode this transport runs in
+    pub fn mode(&self) -> VcrMode {
+        self.mode
+    }
+
+    fn default_redacted() -> Vec<String> {
+        DEFAULT_REDACTED_PARAMS
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Replace the value of every redacted query parameter in `url`
+    ///
+    /// URLs without a redacted parameter are returned unchanged, so their
+    /// encoding still matches cassettes recorded before redaction.
+    fn redact(&self, url: &Url) -> String {
+        let is_redacted = |name: &str| self.redacted.iter().any(|r| r == name);
+        if !url.query_pairs().any(|(name, _)| is_redacted(&name)) {
+            return url.to_string();
+        }
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_redacted(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+        let mut redacted = url.clone();
+        redacted.query_pairs_mut().clear().extend_pairs(pairs);
+        redacted.to_string()
+    }
+
+    /// Normalize a URL so that query parameter order does not affect matching
+    fn match_key(url: &str) -> String {
+        match url.split_once('?') {
+            Some((base, query)) => {
+                let mut pairs: Vec<&str> = query.split('&').collect();
+                pairs.sort_unstable();
+                format!("{}?{}", base, pairs.join("&"))
+            }
+            None => url.to_string(),
+        }
+    }
+
+    /// Build a response from a recorded interaction
+    fn to_response(interaction: &Interaction) -> Result<Response, ClientError> {
+        let mut builder = http::Response::builder().status(interaction.status);
+        for (name, value) in &interaction.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .body(interaction.body.clone())
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+        Ok(Response::from(response))
+    }
+
+    /// Forward a request to the inner transport and save the interaction
+    async fn record_request(
+        &self,
+        client: &Client,
+        request: Request,
+    ) -> Result<Response, ClientError> {
+        let method = request.method().to_string();
+        let url = self.redact(request.url());
+        let response = self.inner.execute(client, request).await?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = String::from_utf8_lossy(&response.bytes().await?).into_owned();
+
+        let interaction = Interaction {
+            method,
+            url,
+            status,
+            headers,
+            body,
+        };
+        let response = Self::to_response(&interaction)?;
+
+        let cassette = {
+            let mut interactions = self.interactions.lock().unwrap();
+            interactions.push(interaction);
+            serde_json::to_string_pretty(&*interactions)
+                .map_err(|e| ClientError::Transport(e.to_string()))?
+        };
+        fs::write(&self.path, cassette).map_err(|e| {
+            ClientError::Transport(format!(
+                "cannot write cassette {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+
+        Ok(response)
+    }
+
+    /// Serve the first not-yet-replayed interaction matching the request
+    ///
+    /// Interactions match on method and redacted URL, ignoring query
+    /// parameter order.
+    fn replay_request(&self, request: &Request) -> Result<Response, ClientError> {
+        let method = request.method().as_str();
+        let url = self.redact(request.url());
+        let key = Self::match_key(&url);
+        let interactions = self.interactions.lock().unwrap();
+        let mut replayed = self.replayed.lock().unwrap();
+
+        let index = interactions
+            .iter()
+            .enumerate()
+            .position(|(i, interaction)| {
+                !replayed[i]
+                    && interaction.method == method
+                    && Self::match_key(&interaction.url) == key
+            })
+            .ok_or_else(|| {
+                ClientError::Transport(format!("no recorded interaction for {} {}", method, url))
+            })?;
+
+        replayed[index] = true;
+        Self::to_response(&interactions[index])
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn execute(&self, client: &Client, request: Request) -> Result<Response, ClientError> {
+        match self.mode {
+            VcrMode::Record => self.record_request(client, request).await,
+            VcrMode::Replay => self.replay_request(&request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Transport answering every request with the same JSON body
+    struct Canned;
+
+    #[async_trait]
+    impl Transport for Canned {
+        async fn execute(&self, _: &Client, _: Request) -> Result<Response, ClientError> {
+            let response = http::Response::builder()
+                .status(200)
+                .body(r#"{"ok":true}"#.to_string())
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    fn get(url: &str) -> Request {
+        Client::new().get(url).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn recorded_urls_redact_credential_params() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let recorder =
+            RecordingTransport::record_with(&path, Arc::new(Canned)).with_redacted_param("secret");
+
+        let request = get("http://api.test/articles/?api_key=k1&secret=s1&limit=5");
+        recorder.execute(&Client::new(), request).await.unwrap();
+
+        let cassette = fs::read_to_string(&path).unwrap();
+        assert!(!cassette.contains("k1"));
+        assert!(!cassette.contains("s1"));
+        assert!(cassette.contains("api_key=REDACTED"));
+        assert!(cassette.contains("secret=REDACTED"));
+        assert!(cassette.contains("limit=5"));
+    }
+
+    #[tokio::test]
+    async fn replay_matches_whatever_the_credential_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+        let recorder = RecordingTransport::record_with(&path, Arc::new(Canned));
+        let request = get("http://api.test/articles/?limit=5&api_key=recorded");
+        recorder.execute(&Client::new(), request).await.unwrap();
+
+        let player = RecordingTransport::replay(&path).unwrap();
+        let request = get("http://api.test/articles/?api_key=other&limit=5");
+        let response = player.execute(&Client::new(), request).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), r#"{"ok":true}"#);
+    }
+
+    #[test]
+    fn urls_without_redacted_params_are_unchanged() {
+        let recorder = RecordingTransport::record("unused.json");
+        let url = Url::parse("http://api.test/articles/?title_contains=a%20b").unwrap();
+        assert_eq!(recorder.redact(&url), url.as_str());
+    }
+}
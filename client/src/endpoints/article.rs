@@ -1,16 +1,29 @@
 use super::{author::Author, event::Event, launch::Launch};
+use crate::utils::deserialize::{flexible_bool, flexible_u32};
+use crate::utils::hash;
+use crate::utils::text::collapse_whitespace;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Article {
+    #[serde(deserialize_with = "flexible_u32")]
     pub id: u32,
     pub title: String,
     pub url: String,
-    pub image_url: String,
+    /// `None` when the API sends `null` or leaves the field out
+    #[serde(default, alias = "imageUrl")]
+    pub image_url: Option<String>,
+    #[serde(alias = "newsSite")]
     pub news_site: String,
-    pub summary: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(alias = "publishedAt")]
     pub published_at: String,
+    #[serde(alias = "updatedAt")]
     pub updated_at: String,
+    #[serde(default, deserialize_with = "flexible_bool")]
     pub featured: bool,
     #[serde(default)]
     pub authors: Vec<Author>,
@@ -19,3 +32,179 @@ pub struct Article {
     #[serde(default)]
     pub events: Vec<Event>,
 }
+
+impl Article {
+    /// Trim and collapse whitespace in `title` and `news_site`
+    ///
+    /// Deserialization keeps the API's exact bytes; call this when the
+    /// values are used for display or deduplication.
+    pub fn normalize(&mut self) {
+        self.title = collapse_whitespace(&self.title);
+        self.news_site = collapse_whitespace(&self.news_site);
+    }
+
+    /// Rectangular copy of the article for CSV and other tabular output
+    ///
+    /// Scalar fields are copied as received. Nested lists become:
+    ///
+    /// - `authors`: author names in order, joined with `"; "`, empty if
+    ///   there are none
+    /// - `launch_count`, `event_count`: lengths of `launches` and `events`
+    /// - `launch_provider`: provider of the first launch, `None` without
+    ///   launches
+    ///
+    /// Launch and event IDs and author socials are dropped.
+    pub fn flatten(&self) -> FlatArticle {
+        FlatArticle {
+            id: self.id,
+            title: self.title.clone(),
+            url: self.url.clone(),
+            image_url: self.image_url.clone(),
+            news_site: self.news_site.clone(),
+            summary: self.summary.clone(),
+            published_at: self.published_at.clone(),
+            updated_at: self.updated_at.clone(),
+            featured: self.featured,
+            authors: self
+                .authors
+                .iter()
+                .map(|author| author.name.as_str())
+                .collect::<Vec<_>>()
+                .join("; "),
+            launch_count: self.launches.len(),
+            event_count: self.events.len(),
+            launch_provider: self
+                .launches
+                .first()
+                .map(|launch| launch.provider.to_string()),
+        }
+    }
+
+    /// Stable content hash, per the rules of `utils::hash::content_hash`
+    pub fn content_hash(&self) -> u64 {
+        hash::content_hash(
+            &self.title,
+            self.summary.as_deref(),
+            &self.url,
+            &self.authors,
+        )
+    }
+}
+
+/// An [`Article`] with its nested lists flattened, from [`Article::flatten`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlatArticle {
+    pub id: u32,
+    pub title: String,
+    pub url: String,
+    pub image_url: Option<String>,
+    pub news_site: String,
+    pub summary: Option<String>,
+    pub published_at: String,
+    pub updated_at: String,
+    pub featured: bool,
+    /// Author names joined with `"; "`
+    pub authors: String,
+    pub launch_count: usize,
+    pub event_count: usize,
+    /// Provider of the first launch
+    pub launch_provider: Option<String>,
+}
+
+/// Sorted, deduplicated `news_site` values of `items`
+///
+/// Names are whitespace-normalized before comparing, as in
+/// [`Article::normalize`].
+pub fn distinct_news_sites(items: &[Article]) -> Vec<String> {
+    let mut sites: Vec<String> = items
+        .iter()
+        .map(|article| collapse_whitespace(&article.news_site))
+        .filter(|site| !site.is_empty())
+        .collect();
+    sites.sort();
+    sites.dedup();
+    sites
+}
+
+/// All launches referenced by `articles`, deduplicated by launch ID
+///
+/// Launches keep the order they are first seen in. When the same ID
+/// appears with different `provider` values, the first occurrence wins.
+pub fn collect_launches(articles: &[Article]) -> Vec<Launch> {
+    let mut seen = HashSet::new();
+    articles
+        .iter()
+        .flat_map(|article| &article.launches)
+        .filter(|launch| seen.insert(launch.id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// All events referenced by `articles`, deduplicated by event ID
+///
+/// Deduplication follows [`collect_launches`]: first-seen order, and the
+/// first `provider` wins for a repeated ID.
+pub fn collect_events(articles: &[Article]) -> Vec<Event> {
+    let mut seen = HashSet::new();
+    articles
+        .iter()
+        .flat_map(|article| &article.events)
+        .filter(|event| seen.insert(event.id))
+        .cloned()
+        .collect()
+}
+
+/// Aggregate statistics over a set of articles
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DatasetSummary {
+    /// Number of articles
+    pub total: usize,
+    /// Sorted, deduplicated news sites, as from [`distinct_news_sites`]
+    pub news_sites: Vec<String>,
+    /// Earliest parseable `published_at`
+    pub earliest_published: Option<DateTime<Utc>>,
+    /// Latest parseable `published_at`
+    pub latest_published: Option<DateTime<Utc>>,
+    /// Number of featured articles
+    pub featured: usize,
+    /// Every author with their article count, most articles first and ties
+    /// broken by name; take the first N for a "top authors" list
+    pub top_authors: Vec<(String, usize)>,
+}
+
+/// Summarize a set of articles
+///
+/// Articles whose `published_at` is not RFC 3339 are left out of the date
+/// range but counted everywhere else. An author listed twice on one
+/// article counts once for it.
+pub fn summarize(items: &[Article]) -> DatasetSummary {
+    let published: Vec<DateTime<Utc>> = items
+        .iter()
+        .filter_map(|article| DateTime::parse_from_rfc3339(&article.published_at).ok())
+        .map(|published_at| published_at.with_timezone(&Utc))
+        .collect();
+
+    let mut author_counts: HashMap<String, usize> = HashMap::new();
+    for article in items {
+        let names: HashSet<String> = article
+            .authors
+            .iter()
+            .map(|author| collapse_whitespace(&author.name))
+            .filter(|name| !name.is_empty())
+            .collect();
+        for name in names {
+            *author_counts.entry(name).or_default() += 1;
+        }
+    }
+    let mut top_authors: Vec<(String, usize)> = author_counts.into_iter().collect();
+    top_authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    DatasetSummary {
+        total: items.len(),
+        news_sites: distinct_news_sites(items),
+        earliest_published: published.iter().min().copied(),
+        latest_published: published.iter().max().copied(),
+        featured: items.iter().filter(|article| article.featured).count(),
+        top_authors,
+    }
+}
@@ -1,8 +1,72 @@
 use super::social::Social;
+use crate::utils::text::collapse_whitespace;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Author {
     pub name: String,
     pub socials: Option<Social>,
 }
+
+/// Directory of unique authors, built up across ingestion runs
+///
+/// Authors are keyed by whitespace-normalized name. Serializes as a map
+/// from that name to the author, so it can be saved and reloaded between
+/// runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuthorIndex {
+    authors: BTreeMap<String, Author>,
+}
+
+impl AuthorIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `authors`, merging socials into authors already in the index
+    ///
+    /// For each social link, a non-empty value from a later merge replaces
+    /// the stored one, so links that change are kept current. Missing or
+    /// empty values never clear a stored link.
+    pub fn merge(&mut self, authors: &[Author]) {
+        for author in authors {
+            let name = collapse_whitespace(&author.name);
+            if name.is_empty() {
+                continue;
+            }
+            let entry = self.authors.entry(name.clone()).or_insert_with(|| Author {
+                name,
+                socials: None,
+            });
+            if let Some(socials) = &author.socials {
+                entry
+                    .socials
+                    .get_or_insert_with(Social::default)
+                    .merge(socials);
+            }
+        }
+    }
+
+    /// Look up an author by name
+    pub fn get(&self, name: &str) -> Option<&Author> {
+        self.authors.get(&collapse_whitespace(name))
+    }
+
+    /// Number of authors in the index
+    pub fn len(&self) -> usize {
+        self.authors.len()
+    }
+
+    /// Whether the index has no authors
+    pub fn is_empty(&self) -> bool {
+        self.authors.is_empty()
+    }
+
+    /// All authors, sorted by name
+    pub fn to_vec(&self) -> Vec<Author> {
+        self.authors.values().cloned().collect()
+    }
+}
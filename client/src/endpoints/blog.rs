@@ -1,21 +1,50 @@
 use super::{author::Author, event::Event, launch::Launch};
+use crate::utils::deserialize::{flexible_bool, flexible_u32};
+use crate::utils::hash;
+use crate::utils::text::collapse_whitespace;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Blog {
+    #[serde(deserialize_with = "flexible_u32")]
     pub id: u32,
     pub title: String,
     #[serde(default)]
     pub authors: Vec<Author>,
     pub url: String,
-    pub image_url: String,
+    /// `None` when the API sends `null` or leaves the field out
+    #[serde(default, alias = "imageUrl")]
+    pub image_url: Option<String>,
+    #[serde(alias = "newsSite")]
     pub news_site: String,
-    pub summary: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(alias = "publishedAt")]
     pub published_at: String,
+    #[serde(alias = "updatedAt")]
     pub updated_at: String,
+    #[serde(default, deserialize_with = "flexible_bool")]
     pub featured: bool,
     #[serde(default)]
     pub launches: Vec<Launch>,
     #[serde(default)]
     pub events: Vec<Event>,
 }
+
+impl Blog {
+    /// Trim and collapse whitespace in `title` and `news_site`
+    pub fn normalize(&mut self) {
+        self.title = collapse_whitespace(&self.title);
+        self.news_site = collapse_whitespace(&self.news_site);
+    }
+
+    /// Stable content hash, per the rules of `utils::hash::content_hash`
+    pub fn content_hash(&self) -> u64 {
+        hash::content_hash(
+            &self.title,
+            self.summary.as_deref(),
+            &self.url,
+            &self.authors,
+        )
+    }
+}
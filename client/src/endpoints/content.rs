@@ -0,0 +1,57 @@
+use super::{article::Article, blog::Blog, report::Report};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An item from any of the content endpoints, tagged with its source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContentItem {
+    Article(Article),
+    Blog(Blog),
+    Report(Report),
+}
+
+impl ContentItem {
+    /// ID within the item's own endpoint
+    pub fn id(&self) -> u32 {
+        match self {
+            ContentItem::Article(article) => article.id,
+            ContentItem::Blog(blog) => blog.id,
+            ContentItem::Report(report) => report.id,
+        }
+    }
+
+    /// Title of the item
+    pub fn title(&self) -> &str {
+        match self {
+            ContentItem::Article(article) => &article.title,
+            ContentItem::Blog(blog) => &blog.title,
+            ContentItem::Report(report) => &report.title,
+        }
+    }
+
+    /// URL of the item on its news site
+    pub fn url(&self) -> &str {
+        match self {
+            ContentItem::Article(article) => &article.url,
+            ContentItem::Blog(blog) => &blog.url,
+            ContentItem::Report(report) => &report.url,
+        }
+    }
+
+    /// Raw `published_at` value as sent by the API
+    pub fn published_at(&self) -> &str {
+        match self {
+            ContentItem::Article(article) => &article.published_at,
+            ContentItem::Blog(blog) => &blog.published_at,
+            ContentItem::Report(report) => &report.published_at,
+        }
+    }
+
+    /// `published_at` parsed as a UTC timestamp, if it is valid RFC 3339
+    pub fn published_at_utc(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.published_at())
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+    }
+}
@@ -1,8 +1,67 @@
+use super::provider::Provider;
+use crate::utils::deserialize::flexible_u32;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
-    #[serde(rename = "event_id")]
+    /// LL2 event ID, read from `event_id` (or v3's `id`) and always
+    /// written back as `event_id`
+    #[serde(rename = "event_id", alias = "id", deserialize_with = "flexible_u32")]
     pub id: u32,
-    pub provider: String,
+    pub provider: Provider,
+}
+
+/// Full event record from Launch Library 2
+///
+/// Covers the fields used for an event detail page; the rest of the LL2
+/// record is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDetail {
+    /// LL2 event ID, the same value as [`Event::id`]
+    #[serde(deserialize_with = "flexible_u32")]
+    pub id: u32,
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub event_type: Option<EventType>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    /// ISO 8601 time of the event
+    #[serde(default)]
+    pub date: Option<String>,
+}
+
+/// Kind of event such as "Spacewalk" or "Docking"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventType {
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip_keeps_event_id() {
+        let upstream = json!({ "event_id": 842, "provider": "Launch Library 2" });
+        let event: Event = serde_json::from_value(upstream.clone()).unwrap();
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(serialized, upstream);
+
+        let again: Event = serde_json::from_value(serialized).unwrap();
+        assert_eq!(again.id, event.id);
+        assert_eq!(again.provider, event.provider);
+    }
+
+    #[test]
+    fn v3_id_is_written_back_as_event_id() {
+        let event: Event =
+            serde_json::from_value(json!({ "id": "842", "provider": "Launch Library 2" })).unwrap();
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({ "event_id": 842, "provider": "Launch Library 2" })
+        );
+    }
 }
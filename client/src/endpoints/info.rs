@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Info {
+    pub version: String,
+    #[serde(default, alias = "newsSites")]
+    pub news_sites: Vec<String>,
+}
+
+impl Info {
+    /// Major API version named by `version`, if it can be parsed
+    pub fn api_version(&self) -> Option<ApiVersion> {
+        ApiVersion::parse(&self.version)
+    }
+}
+
+/// Major version of the Spaceflight News API
+///
+/// Field names differ between versions:
+///
+/// | v4             | v3            | Types                    |
+/// |----------------|---------------|--------------------------|
+/// | `image_url`    | `imageUrl`    | articles, blogs, reports |
+/// | `news_site`    | `newsSite`    | articles, blogs, reports |
+/// | `published_at` | `publishedAt` | articles, blogs, reports |
+/// | `updated_at`   | `updatedAt`   | articles, blogs, reports |
+/// | `launch_id`    | `id`          | launches                 |
+/// | `event_id`     | `id`          | events                   |
+/// | `news_sites`   | `newsSites`   | `info/`                  |
+///
+/// v3 has no `authors`; they read as empty. The content structs accept
+/// both spellings, so the same types decode responses of either version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiVersion {
+    V3,
+    V4,
+}
+
+impl ApiVersion {
+    /// Read the major version from a version string such as `"4.16.1"`
+    ///
+    /// Versions after 4 are treated as 4, whose field names they keep.
+    pub fn parse(version: &str) -> Option<Self> {
+        let major: u32 = version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split('.')
+            .next()?
+            .parse()
+            .ok()?;
+        match major {
+            3 => Some(ApiVersion::V3),
+            m if m >= 4 => Some(ApiVersion::V4),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiVersion::V3 => write!(f, "v3"),
+            ApiVersion::V4 => write!(f, "v4"),
+        }
+    }
+}
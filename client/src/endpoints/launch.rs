@@ -1,8 +1,110 @@
+use super::provider::Provider;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Launch {
-    #[serde(rename = "launch_id")]
+    /// LL2 UUID, read from `launch_id` (or v3's `id`) and always written
+    /// back as `launch_id`
+    #[serde(rename = "launch_id", alias = "id")]
     pub id: String,
-    pub provider: String,
+    pub provider: Provider,
+}
+
+/// Full launch record from Launch Library 2
+///
+/// Covers the fields used for a launch detail page; the rest of the LL2
+/// record is ignored. Timestamps are kept as the API's ISO 8601 strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchDetail {
+    /// LL2 UUID, the same value as [`Launch::id`]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub status: Option<LaunchStatus>,
+    /// Best current estimate of the launch time
+    #[serde(default)]
+    pub net: Option<String>,
+    #[serde(default)]
+    pub window_start: Option<String>,
+    #[serde(default)]
+    pub window_end: Option<String>,
+    #[serde(default)]
+    pub launch_service_provider: Option<Agency>,
+    #[serde(default)]
+    pub pad: Option<Pad>,
+    #[serde(default)]
+    pub mission: Option<Mission>,
+}
+
+/// Launch status such as "Go for Launch" or "Launch Successful"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchStatus {
+    pub name: String,
+    #[serde(default)]
+    pub abbrev: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Organization operating a launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agency {
+    pub name: String,
+    #[serde(default)]
+    pub abbrev: Option<String>,
+}
+
+/// Launch pad and the site it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pad {
+    pub name: String,
+    #[serde(default)]
+    pub location: Option<PadLocation>,
+}
+
+/// Launch site of a pad
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PadLocation {
+    pub name: String,
+}
+
+/// Mission flown by a launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mission {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub mission_type: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn round_trip_keeps_launch_id() {
+        let upstream = json!({
+            "launch_id": "e3df2ecd-c239-472f-95e4-2b89b4f75800",
+            "provider": "Launch Library 2",
+        });
+        let launch: Launch = serde_json::from_value(upstream.clone()).unwrap();
+        let serialized = serde_json::to_value(&launch).unwrap();
+        assert_eq!(serialized, upstream);
+
+        let again: Launch = serde_json::from_value(serialized).unwrap();
+        assert_eq!(again.id, launch.id);
+        assert_eq!(again.provider, launch.provider);
+    }
+
+    #[test]
+    fn v3_id_is_written_back_as_launch_id() {
+        let launch: Launch =
+            serde_json::from_value(json!({ "id": "abc", "provider": "Launch Library 2" })).unwrap();
+        assert_eq!(
+            serde_json::to_value(&launch).unwrap(),
+            json!({ "launch_id": "abc", "provider": "Launch Library 2" })
+        );
+    }
 }
@@ -1,9 +1,135 @@
 pub mod article;
 pub mod author;
 pub mod blog;
+pub mod content;
 pub mod event;
+pub mod info;
 pub mod launch;
 pub mod paginated;
+pub mod provider;
 pub mod report;
 pub mod social;
 
+#[cfg(test)]
+mod tests {
+    use super::{article::Article, blog::Blog, report::Report};
+    use crate::feed::{FeedMeta, to_rss};
+    use crate::index::ContentIndex;
+
+    const IMAGE_URL_NULL: &str = include_str!("fixtures/image_url_null.json");
+    const IMAGE_URL_MISSING: &str = include_str!("fixtures/image_url_missing.json");
+    const FEATURED_MISSING: &str = include_str!("fixtures/featured_missing.json");
+    const SUMMARY_NULL: &str = include_str!("fixtures/summary_null.json");
+    const SUMMARY_MISSING: &str = include_str!("fixtures/summary_missing.json");
+    const STRING_IDS: &str = include_str!("fixtures/string_ids.json");
+
+    #[test]
+    fn null_or_missing_image_url_is_none() {
+        for fixture in [IMAGE_URL_NULL, IMAGE_URL_MISSING] {
+            let article: Article = serde_json::from_str(fixture).unwrap();
+            assert_eq!(article.image_url, None);
+            let blog: Blog = serde_json::from_str(fixture).unwrap();
+            assert_eq!(blog.image_url, None);
+            let report: Report = serde_json::from_str(fixture).unwrap();
+            assert_eq!(report.image_url, None);
+        }
+    }
+
+    #[test]
+    fn numeric_string_ids_are_parsed() {
+        let article: Article = serde_json::from_str(STRING_IDS).unwrap();
+        assert_eq!(article.id, 31234);
+        assert_eq!(article.events[0].id, 812);
+        let blog: Blog = serde_json::from_str(STRING_IDS).unwrap();
+        assert_eq!(blog.id, 31234);
+        let report: Report = serde_json::from_str(STRING_IDS).unwrap();
+        assert_eq!(report.id, 31234);
+    }
+
+    #[test]
+    fn non_numeric_string_ids_are_rejected() {
+        let fixture = STRING_IDS.replace(r#""id": "31234""#, r#""id": "abc""#);
+        assert!(serde_json::from_str::<Article>(&fixture).is_err());
+    }
+
+    #[test]
+    fn normalize_cleans_messy_titles_and_keeps_them_otherwise() {
+        let messy = IMAGE_URL_NULL
+            .replace(
+                r#""title": "Starship completes static fire""#,
+                r#""title": "\n  Starship   completes\tstatic fire \n""#,
+            )
+            .replace(
+                r#""news_site": "Example News""#,
+                r#""news_site": " Example  News ""#,
+            );
+
+        let mut article: Article = serde_json::from_str(&messy).unwrap();
+        assert_eq!(article.title, "\n  Starship   completes\tstatic fire \n");
+        article.normalize();
+        assert_eq!(article.title, "Starship completes static fire");
+        assert_eq!(article.news_site, "Example News");
+
+        let mut blog: Blog = serde_json::from_str(&messy).unwrap();
+        blog.normalize();
+        assert_eq!(blog.title, "Starship completes static fire");
+        assert_eq!(blog.news_site, "Example News");
+
+        let mut report: Report = serde_json::from_str(&messy).unwrap();
+        report.normalize();
+        assert_eq!(report.title, "Starship completes static fire");
+        assert_eq!(report.news_site, "Example News");
+    }
+
+    #[test]
+    fn missing_featured_is_false() {
+        let report: Report = serde_json::from_str(FEATURED_MISSING).unwrap();
+        assert!(!report.featured);
+        let article: Article = serde_json::from_str(FEATURED_MISSING).unwrap();
+        assert!(!article.featured);
+        let blog: Blog = serde_json::from_str(FEATURED_MISSING).unwrap();
+        assert!(!blog.featured);
+    }
+
+    #[test]
+    fn report_featured_round_trips() {
+        let mut report: Report = serde_json::from_str(FEATURED_MISSING).unwrap();
+        report.featured = true;
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["featured"], true);
+        let report: Report = serde_json::from_value(json).unwrap();
+        assert!(report.featured);
+    }
+
+    #[test]
+    fn null_or_missing_summary_is_none() {
+        for fixture in [SUMMARY_NULL, SUMMARY_MISSING] {
+            let article: Article = serde_json::from_str(fixture).unwrap();
+            assert_eq!(article.summary, None);
+            let blog: Blog = serde_json::from_str(fixture).unwrap();
+            assert_eq!(blog.summary, None);
+            let report: Report = serde_json::from_str(fixture).unwrap();
+            assert_eq!(report.summary, None);
+        }
+    }
+
+    #[test]
+    fn articles_without_summaries_are_indexed_and_rendered() {
+        let articles: Vec<Article> = [SUMMARY_NULL, SUMMARY_MISSING]
+            .iter()
+            .map(|fixture| serde_json::from_str(fixture).unwrap())
+            .collect();
+        assert_eq!(ContentIndex::new(&articles).search("starship").len(), 2);
+
+        let rss = to_rss(
+            &articles,
+            FeedMeta {
+                title: "News".to_string(),
+                link: "https://example.com".to_string(),
+                description: "Latest".to_string(),
+            },
+        );
+        // Only the channel has a description
+        assert_eq!(rss.matches("<description>").count(), 1);
+    }
+}
@@ -1,9 +1,36 @@
+use super::article::{Article, FlatArticle};
+use crate::utils::deserialize::optional_link;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Paginated<T> {
     pub count: u32,
+    #[serde(default, deserialize_with = "optional_link")]
     pub next: Option<String>,
+    #[serde(default, deserialize_with = "optional_link")]
     pub previous: Option<String>,
     pub results: Vec<T>,
-}
\ No newline at end of file
+}
+
+impl<T> Paginated<T> {
+    /// Number of pages of `limit` items needed to cover `count`
+    ///
+    /// A `limit` of 0 is treated as 1.
+    pub fn total_pages(&self, limit: u32) -> u32 {
+        self.count.div_ceil(limit.max(1))
+    }
+}
+
+impl Paginated<Article> {
+    /// The page with every article flattened by [`Article::flatten`]
+    ///
+    /// `count`, `next` and `previous` are kept unchanged.
+    pub fn flatten(&self) -> Paginated<FlatArticle> {
+        Paginated {
+            count: self.count,
+            next: self.next.clone(),
+            previous: self.previous.clone(),
+            results: self.results.iter().map(Article::flatten).collect(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Source of a launch or event record
+///
+/// Well-known names map to their own variant; anything else is kept as
+/// `Other`. Matching is exact, so `as_str` always returns the string the
+/// API sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Provider {
+    /// "Launch Library 2", the source the API currently reports
+    LaunchLibrary2,
+    SpaceX,
+    Ula,
+    Roscosmos,
+    Nasa,
+    Esa,
+    RocketLab,
+    BlueOrigin,
+    /// Any other provider, as sent by the API
+    Other(String),
+}
+
+impl Provider {
+    /// The provider name as sent by the API
+    pub fn as_str(&self) -> &str {
+        match self {
+            Provider::LaunchLibrary2 => "Launch Library 2",
+            Provider::SpaceX => "SpaceX",
+            Provider::Ula => "ULA",
+            Provider::Roscosmos => "Roscosmos",
+            Provider::Nasa => "NASA",
+            Provider::Esa => "ESA",
+            Provider::RocketLab => "Rocket Lab",
+            Provider::BlueOrigin => "Blue Origin",
+            Provider::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for Provider {
+    fn from(name: &str) -> Self {
+        match name {
+            "Launch Library 2" => Provider::LaunchLibrary2,
+            "SpaceX" => Provider::SpaceX,
+            "ULA" => Provider::Ula,
+            "Roscosmos" => Provider::Roscosmos,
+            "NASA" => Provider::Nasa,
+            "ESA" => Provider::Esa,
+            "Rocket Lab" => Provider::RocketLab,
+            "Blue Origin" => Provider::BlueOrigin,
+            other => Provider::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for Provider {
+    fn from(name: String) -> Self {
+        match Provider::from(name.as_str()) {
+            Provider::Other(_) => Provider::Other(name),
+            known => known,
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Provider {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Provider {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Provider::from)
+    }
+}
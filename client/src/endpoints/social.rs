@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Social {
     #[serde(rename = "x")]
     pub twitter: Option<String>,
@@ -11,3 +11,22 @@ pub struct Social {
     pub mastodon: Option<String>,
     pub bluesky: Option<String>,
 }
+
+impl Social {
+    /// Take every non-empty link from `other`, keeping ours where it has none
+    pub fn merge(&mut self, other: &Social) {
+        let take = |ours: &mut Option<String>, theirs: &Option<String>| {
+            if let Some(link) = theirs
+                && !link.trim().is_empty()
+            {
+                *ours = Some(link.clone());
+            }
+        };
+        take(&mut self.twitter, &other.twitter);
+        take(&mut self.youtube, &other.youtube);
+        take(&mut self.instagram, &other.instagram);
+        take(&mut self.linkedin, &other.linkedin);
+        take(&mut self.mastodon, &other.mastodon);
+        take(&mut self.bluesky, &other.bluesky);
+    }
+}
@@ -0,0 +1,445 @@
+//! Error type shared by the REST client and its transports
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Errors returned by `RESTClient` operations
+#[derive(Debug)]
+pub enum ClientError {
+    /// The HTTP request failed or its body could not be read
+    ///
+    /// The error's URL has its query string removed, since it may carry an
+    /// API key.
+    Http(reqwest::Error),
+    /// Writing a response body to its destination failed
+    Io(std::io::Error),
+    /// The response body was not the expected JSON
+    Decode(serde_json::Error),
+    /// The API answered with a non-2xx status
+    Api {
+        /// HTTP status code
+        status: u16,
+        /// Message extracted from the error body
+        detail: String,
+        /// The error body's structured fields, where it had any
+        body: Box<ApiErrorBody>,
+        /// Wait requested by the response's `Retry-After` header
+        retry_after: Option<std::time::Duration>,
+    },
+    /// A query builder was given parameters the API would reject
+    InvalidQuery(String),
+    /// The transport could not produce a response
+    Transport(String),
+    /// A schema lookup, query build or schema application failed
+    Schema(String),
+    /// A schema-aware method was called on a client without schemas
+    NoSchemaManager,
+    /// The operation was cancelled through its cancellation token
+    Cancelled,
+    /// A response body exceeded the client's `max_response_bytes`
+    TooLarge {
+        /// The configured limit in bytes
+        limit: u64,
+    },
+    /// An article image was missing or the URL did not serve an image
+    Image(String),
+    /// An endpoint had no items where at least one was needed
+    Empty {
+        /// Endpoint that was queried
+        endpoint: String,
+    },
+    /// Pagination ended before the API's reported `count` was reached
+    IncompletePagination {
+        /// Number of items actually collected
+        collected: usize,
+        /// Number of items the API reported
+        expected: usize,
+    },
+    /// `info/` reported an API version this client cannot read
+    UnsupportedVersion(String),
+    /// The circuit breaker is open after repeated failures, so the request
+    /// was not sent
+    CircuitOpen {
+        /// Time until a request will be let through again
+        retry_after: std::time::Duration,
+    },
+    /// A `206 Partial Content` response did not start at the requested byte
+    RangeMismatch {
+        /// First byte asked for
+        start: u64,
+        /// The response's `Content-Range`, if it had a readable one
+        content_range: Option<String>,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(e) => write!(f, "HTTP error: {}", e),
+            ClientError::Io(e) => write!(f, "I/O error: {}", e),
+            ClientError::Decode(e) => write!(f, "Decode error: {}", e),
+            ClientError::Api { status, detail, .. } => {
+                write!(f, "API error {}: {}", status, detail)
+            }
+            ClientError::InvalidQuery(message) => write!(f, "Invalid query: {}", message),
+            ClientError::Transport(message) => write!(f, "Transport error: {}", message),
+            ClientError::Schema(message) => write!(f, "Schema error: {}", message),
+            ClientError::NoSchemaManager => write!(f, "No schema manager configured"),
+            ClientError::Cancelled => write!(f, "Operation cancelled"),
+            ClientError::TooLarge { limit } => {
+                write!(f, "Response body exceeded {} bytes", limit)
+            }
+            ClientError::Image(message) => write!(f, "Image error: {}", message),
+            ClientError::Empty { endpoint } => write!(f, "No items available at '{}'", endpoint),
+            ClientError::IncompletePagination {
+                collected,
+                expected,
+            } => write!(
+                f,
+                "Pagination ended after {} of {} reported items",
+                collected, expected
+            ),
+            ClientError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported API version '{}'", version)
+            }
+            ClientError::CircuitOpen { retry_after } => write!(
+                f,
+                "Circuit open after repeated failures; retry in {:.1}s",
+                retry_after.as_secs_f64()
+            ),
+            ClientError::RangeMismatch {
+                start,
+                content_range: Some(content_range),
+            } => write!(
+                f,
+                "Asked for bytes from {}, got Content-Range '{}'",
+                start, content_range
+            ),
+            ClientError::RangeMismatch {
+                start,
+                content_range: None,
+            } => write!(
+                f,
+                "Asked for bytes from {}, got a partial response without Content-Range",
+                start
+            ),
+        }
+    }
+}
+
+impl ClientError {
+    /// Build an `Api` error from a status code and raw response body
+    ///
+    /// Understands the `{"detail": "..."}` and `{"errors": [...]}` bodies the
+    /// API returns, and field-validation maps (see [`ApiErrorBody`]), falling
+    /// back to the raw body or the status reason. `retry_after` is left
+    /// unset, since it comes from the headers.
+    pub fn from_response_body(status: reqwest::StatusCode, body: &str) -> Self {
+        let json = serde_json::from_str::<Value>(body).ok();
+        let parsed = json
+            .as_ref()
+            .map(ApiErrorBody::from_json)
+            .unwrap_or_default();
+        let detail = json
+            .and_then(|json| {
+                if let Some(detail) = &parsed.detail {
+                    return Some(detail.clone());
+                }
+                if !parsed.field_errors.is_empty() {
+                    return Some(parsed.summary());
+                }
+                let errors = json.get("errors")?.as_array()?;
+                let messages: Vec<String> = errors
+                    .iter()
+                    .map(|error| match error {
+                        Value::String(message) => message.clone(),
+                        other => other
+                            .get("detail")
+                            .or_else(|| other.get("message"))
+                            .and_then(|v| v.as_str())
+                            .map_or_else(|| other.to_string(), str::to_string),
+                    })
+                    .collect();
+                (!messages.is_empty()).then(|| messages.join("; "))
+            })
+            .or_else(|| {
+                let body = body.trim();
+                (!body.is_empty()).then(|| body.to_string())
+            })
+            .unwrap_or_else(|| {
+                status
+                    .canonical_reason()
+                    .unwrap_or("Unknown error")
+                    .to_string()
+            });
+
+        ClientError::Api {
+            status: status.as_u16(),
+            detail,
+            body: Box::new(parsed),
+            retry_after: None,
+        }
+    }
+}
+
+/// Keys holding an error body's message, in order of preference
+const MESSAGE_KEYS: &[&str] = &["detail", "message", "error"];
+
+/// Keys of a field-validation body that are not field names
+const NON_FIELD_KEYS: &[&str] = &["code", "errors", "status"];
+
+/// Structured contents of an API error body
+///
+/// Covers the simple form, `{"detail": "Not found.", "code": "not_found"}`,
+/// and field-validation maps such as
+/// `{"limit": ["Ensure this value is less than or equal to 100."]}`, where
+/// each key names the rejected parameter (or `non_field_errors`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiErrorBody {
+    /// The `detail` (or `message`, or `error`) message, if the body had one
+    pub detail: Option<String>,
+    /// The machine-readable `code`, if the body had one
+    pub code: Option<String>,
+    /// Messages for each rejected field
+    pub field_errors: HashMap<String, Vec<String>>,
+}
+
+impl ApiErrorBody {
+    /// Parse an error body, leaving every field empty if it isn't JSON
+    pub fn parse(body: &str) -> Self {
+        serde_json::from_str::<Value>(body)
+            .map(|json| Self::from_json(&json))
+            .unwrap_or_default()
+    }
+
+    /// Read the structured fields from a parsed error body
+    ///
+    /// The message is read from `detail`, or failing that `message` or
+    /// `error`. A body with any of those is a plain error, so its other
+    /// keys are not field errors. Otherwise every key but `code`, `errors`
+    /// and `status` whose value is a string or an array of strings is
+    /// taken as a field error.
+    pub fn from_json(json: &Value) -> Self {
+        let Some(object) = json.as_object() else {
+            return Self::default();
+        };
+        let text = |key: &str| object.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        let detail = MESSAGE_KEYS.iter().find_map(|key| text(key));
+        let has_message = MESSAGE_KEYS.iter().any(|key| object.contains_key(*key));
+        let mut field_errors = HashMap::new();
+        for (field, value) in object {
+            if has_message || NON_FIELD_KEYS.contains(&field.as_str()) {
+                continue;
+            }
+            let messages: Vec<String> = match value {
+                Value::String(message) => vec![message.clone()],
+                Value::Array(values) => values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if !messages.is_empty() {
+                field_errors.insert(field.clone(), messages);
+            }
+        }
+
+        Self {
+            detail,
+            code: text("code"),
+            field_errors,
+        }
+    }
+
+    /// Field errors as `field: message` pairs joined by `; `, sorted by field
+    fn summary(&self) -> String {
+        let mut fields: Vec<_> = self.field_errors.iter().collect();
+        fields.sort();
+        fields
+            .into_iter()
+            .flat_map(|(field, messages)| {
+                messages
+                    .iter()
+                    .map(move |message| format!("{}: {}", field, message))
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+impl ClientError {
+    /// Copy an error for a caller that shares another caller's request
+    ///
+    /// `Http`, `Io` and `Decode` wrap errors that cannot be cloned, so they are
+    /// carried over as `Transport` with the same message.
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            ClientError::Http(_) | ClientError::Io(_) | ClientError::Decode(_) => {
+                ClientError::Transport(self.to_string())
+            }
+            ClientError::Api {
+                status,
+                detail,
+                body,
+                retry_after,
+            } => ClientError::Api {
+                status: *status,
+                detail: detail.clone(),
+                body: body.clone(),
+                retry_after: *retry_after,
+            },
+            ClientError::InvalidQuery(message) => ClientError::InvalidQuery(message.clone()),
+            ClientError::Transport(message) => ClientError::Transport(message.clone()),
+            ClientError::Schema(message) => ClientError::Schema(message.clone()),
+            ClientError::NoSchemaManager => ClientError::NoSchemaManager,
+            ClientError::Cancelled => ClientError::Cancelled,
+            ClientError::TooLarge { limit } => ClientError::TooLarge { limit: *limit },
+            ClientError::Image(message) => ClientError::Image(message.clone()),
+            ClientError::Empty { endpoint } => ClientError::Empty {
+                endpoint: endpoint.clone(),
+            },
+            ClientError::IncompletePagination {
+                collected,
+                expected,
+            } => ClientError::IncompletePagination {
+                collected: *collected,
+                expected: *expected,
+            },
+            ClientError::UnsupportedVersion(version) => {
+                ClientError::UnsupportedVersion(version.clone())
+            }
+            ClientError::CircuitOpen { retry_after } => ClientError::CircuitOpen {
+                retry_after: *retry_after,
+            },
+            ClientError::RangeMismatch {
+                start,
+                content_range,
+            } => ClientError::RangeMismatch {
+                start: *start,
+                content_range: content_range.clone(),
+            },
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Http(e) => Some(e),
+            ClientError::Io(e) => Some(e),
+            ClientError::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(mut e: reqwest::Error) -> Self {
+        if let Some(url) = e.url_mut() {
+            url.set_query(None);
+        }
+        ClientError::Http(e)
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(e: serde_json::Error) -> Self {
+        ClientError::Decode(e)
+    }
+}
+
+impl From<Box<dyn std::error::Error>> for ClientError {
+    fn from(e: Box<dyn std::error::Error>) -> Self {
+        ClientError::Schema(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn detail_body() {
+        let body = ApiErrorBody::parse(r#"{"detail": "Not found.", "code": "not_found"}"#);
+        assert_eq!(
+            body,
+            ApiErrorBody {
+                detail: Some("Not found.".to_string()),
+                code: Some("not_found".to_string()),
+                field_errors: HashMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn field_validation_body() {
+        let body = ApiErrorBody::parse(
+            r#"{"limit": ["Ensure this value is less than or equal to 100."], "offset": "Invalid"}"#,
+        );
+        assert_eq!(body.detail, None);
+        assert_eq!(
+            body.field_errors,
+            HashMap::from([
+                (
+                    "limit".to_string(),
+                    vec!["Ensure this value is less than or equal to 100.".to_string()]
+                ),
+                ("offset".to_string(), vec!["Invalid".to_string()]),
+            ])
+        );
+
+        let error = ClientError::from_response_body(
+            StatusCode::BAD_REQUEST,
+            r#"{"limit": ["Too big."], "offset": ["Negative."]}"#,
+        );
+        assert_eq!(
+            error.to_string(),
+            "API error 400: limit: Too big.; offset: Negative."
+        );
+    }
+
+    #[test]
+    fn message_keys_are_not_field_errors() {
+        for body in [
+            r#"{"message": "Rate limited", "retry": "later"}"#,
+            r#"{"error": "Rate limited", "status": "429"}"#,
+        ] {
+            let body = ApiErrorBody::parse(body);
+            assert_eq!(body.detail.as_deref(), Some("Rate limited"));
+            assert!(body.field_errors.is_empty(), "{:?}", body.field_errors);
+        }
+
+        let error = ClientError::from_response_body(
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"message": "Rate limited"}"#,
+        );
+        assert_eq!(error.to_string(), "API error 429: Rate limited");
+    }
+
+    #[tokio::test]
+    async fn http_errors_drop_the_query_string() {
+        // Nothing listens on port 1, so the request fails with its URL attached
+        let error = reqwest::Client::new()
+            .get("http://127.0.0.1:1/articles/?api_key=secret&limit=5")
+            .send()
+            .await
+            .unwrap_err();
+        let error = ClientError::from(error);
+
+        let message = error.to_string();
+        assert!(message.contains("/articles/"), "{}", message);
+        assert!(!message.contains("secret"), "{}", message);
+        assert!(!format!("{:?}", error).contains("secret"));
+        assert!(!error.duplicate().to_string().contains("secret"));
+    }
+}
@@ -5,10 +5,22 @@
 //! Rust code changes.
 
 use crate::RESTClient;
-use crate::schema::SchemaManager;
+use crate::client::conditional::{Conditional, Validators};
+use crate::client::retry::{DEFAULT_RETRY_DELAY, RetryPolicy};
+use crate::client::transport::{HttpTransport, Transport};
+use crate::metrics::Metrics;
+use crate::schema::{
+    DEFAULT_FLOAT_PRECISION, DEFAULT_MAX_LIMIT, QueryEncoding, QueryParamValue, SchemaManager,
+};
+use crate::sink::{BoxError, ContentSink, StdoutSink};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
 use toml::Value as TomlValue;
 
 /// Configuration for a single API endpoint
@@ -24,15 +36,46 @@ pub struct EndpointConfig {
     pub schema_name: String,
     /// Query parameters for this endpoint
     pub query_params: HashMap<String, String>,
+    /// Only keep results from these news sites (case-insensitive); empty keeps all
+    pub include_sites: Vec<String>,
+    /// Drop results from these news sites (case-insensitive)
+    pub exclude_sites: Vec<String>,
+    /// Retries after a transient failure; overrides `[config] retries`
+    pub retries: Option<u32>,
+    /// Delay between retries in milliseconds; overrides `[config] retry_delay_ms`
+    pub retry_delay_ms: Option<u64>,
+    /// Walk every page by offset and write each to the sink as it arrives,
+    /// rather than fetching one page
+    pub fetch_all: bool,
 }
 
 /// Global configuration
 #[derive(Debug, Clone)]
 pub struct GlobalConfig {
-    /// Output format: "json", "table", or "detailed"
+    /// Output format: "json", "table", "compact", or "detailed"
     pub output_format: String,
     /// Maximum number of items to display per endpoint
     pub max_display_items: usize,
+    /// Largest `limit` sent to the API; larger values are clamped
+    pub max_limit: u32,
+    /// How query values are escaped: `"percent"` (space as `%20`) or
+    /// `"form"` (space as `+`)
+    pub query_encoding: QueryEncoding,
+    /// Decimal places kept in float query values, schema defaults and
+    /// endpoint `query_params` alike
+    pub float_precision: usize,
+    /// Retries after a transient failure, for endpoints that don't set their own
+    pub retries: u32,
+    /// Delay between retries in milliseconds, for endpoints that don't set their own
+    pub retry_delay_ms: u64,
+    /// JSON file that keeps each endpoint's validators between runs; when
+    /// set, requests are conditional and unchanged endpoints are skipped
+    pub state_file: Option<String>,
+    /// Pages fetched at once by `fetch_all` endpoints
+    pub fetch_workers: usize,
+    /// Sink writes run at once by `fetch_all` endpoints; fetching pauses
+    /// while this many pages are being written and as many more are queued
+    pub write_workers: usize,
 }
 
 /// API Executor that runs configurations from TOML files
@@ -40,37 +83,135 @@ pub struct APIExecutor {
     /// Schema manager for handling data schemas
     schema_manager: SchemaManager,
     /// Endpoint configurations
-    endpoints: Vec<EndpointConfig>,
+    endpoints: RwLock<Vec<EndpointConfig>>,
     /// Global configuration
     global_config: GlobalConfig,
+    /// Destination for fetched results
+    sink: Box<dyn ContentSink>,
+    /// Transport shared by every endpoint client
+    transport: Arc<dyn Transport>,
+    /// Request metrics for the current run
+    metrics: Arc<Metrics>,
+    /// Validators from the last response of each endpoint, by name
+    state: Mutex<HashMap<String, Validators>>,
 }
 
 impl APIExecutor {
     /// Create a new API executor from a TOML configuration file
     pub fn from_config_file(config_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let contents = fs::read_to_string(config_path)?;
-        let config: TomlValue = toml::from_str(&contents)?;
+        Self::from_config_str(&contents)
+    }
+
+    /// Create a new API executor from TOML configuration text
+    pub fn from_config_str(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: TomlValue = toml::from_str(contents)?;
+
+        // Parse global configuration
+        let global_config = Self::parse_global_config(&config)?;
 
         // Create a schema manager and load schemas from the same config
         let mut schema_manager = SchemaManager::new();
+        schema_manager.set_max_limit(global_config.max_limit);
+        schema_manager.set_query_encoding(global_config.query_encoding);
+        schema_manager.set_float_precision(global_config.float_precision);
         schema_manager.load_from_toml_value(&config)?;
 
         // Parse endpoint configurations
-        let endpoints = Self::parse_endpoints(&config)?;
+        let endpoints = Self::parse_endpoints(&config, global_config.float_precision)?;
+        Self::check_endpoint_schemas(&endpoints, &schema_manager)?;
 
-        // Parse global configuration
-        let global_config = Self::parse_global_config(&config)?;
+        let sink = Box::new(StdoutSink::new(
+            global_config.output_format.clone(),
+            global_config.max_display_items,
+        ));
+
+        let state = match &global_config.state_file {
+            Some(path) => Self::load_state(path)?,
+            None => HashMap::new(),
+        };
 
         Ok(Self {
             schema_manager,
-            endpoints,
+            endpoints: RwLock::new(endpoints),
             global_config,
+            sink,
+            transport: Arc::new(HttpTransport),
+            metrics: Arc::new(Metrics::new()),
+            state: Mutex::new(state),
         })
     }
 
+    /// Route every endpoint request through a custom transport
+    pub fn with_transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Enable or disable an endpoint by name
+    ///
+    /// Takes effect at the start of the next `execute_all`. Returns `false`
+    /// if no endpoint has that name.
+    pub fn set_endpoint_enabled(&self, name: &str, enabled: bool) -> bool {
+        let mut endpoints = self.endpoints.write().unwrap();
+        match endpoints.iter_mut().find(|endpoint| endpoint.name == name) {
+            Some(endpoint) => {
+                endpoint.enabled = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names of the endpoints that will run on the next `execute_all`
+    pub fn enabled_endpoints(&self) -> Vec<String> {
+        self.endpoints
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|endpoint| endpoint.enabled)
+            .map(|endpoint| endpoint.name.clone())
+            .collect()
+    }
+
+    /// Get the request metrics of the last run
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Get the global configuration
+    pub fn global_config(&self) -> &GlobalConfig {
+        &self.global_config
+    }
+
+    /// Replace the sink that receives fetched results
+    pub fn with_sink(mut self, sink: Box<dyn ContentSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Read saved validators, or start empty if the state file doesn't exist yet
+    fn load_state(path: &str) -> Result<HashMap<String, Validators>, Box<dyn std::error::Error>> {
+        if !Path::new(path).exists() {
+            return Ok(HashMap::new());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write the current validators to the state file, if one is configured
+    fn save_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(path) = &self.global_config.state_file {
+            let state = self.state.lock().unwrap();
+            fs::write(path, serde_json::to_string_pretty(&*state)?)?;
+        }
+        Ok(())
+    }
+
     /// Parse endpoint configurations from TOML
     fn parse_endpoints(
         config: &TomlValue,
+        float_precision: usize,
     ) -> Result<Vec<EndpointConfig>, Box<dyn std::error::Error>> {
         let mut endpoints = Vec::new();
 
@@ -100,13 +241,16 @@ impl APIExecutor {
                         // Parse query parameters
                         let mut query_params = HashMap::new();
                         let query_key = format!("{}.query_params", name);
-                        if let Some(query_section) = tables.get(&query_key) {
-                            if let Some(query_table) = query_section.as_table() {
-                                for (param_name, param_value) in query_table {
-                                    if let Some(value_str) = Self::toml_value_to_string(param_value)
-                                    {
-                                        query_params.insert(param_name.clone(), value_str);
-                                    }
+                        if let Some(query_section) = endpoint_table
+                            .get("query_params")
+                            .or_else(|| tables.get(&query_key))
+                            && let Some(query_table) = query_section.as_table()
+                        {
+                            for (param_name, param_value) in query_table {
+                                if let Some(value_str) =
+                                    Self::toml_value_to_string(param_value, float_precision)
+                                {
+                                    query_params.insert(param_name.clone(), value_str);
                                 }
                             }
                         }
@@ -117,6 +261,20 @@ impl APIExecutor {
                             enabled,
                             schema_name,
                             query_params,
+                            include_sites: Self::parse_string_list(endpoint_table, "include_sites"),
+                            exclude_sites: Self::parse_string_list(endpoint_table, "exclude_sites"),
+                            retries: Self::parse_non_negative(
+                                endpoint_table.get("retries"),
+                                &format!("{}.retries", name),
+                            )?,
+                            retry_delay_ms: Self::parse_non_negative(
+                                endpoint_table.get("retry_delay_ms"),
+                                &format!("{}.retry_delay_ms", name),
+                            )?,
+                            fetch_all: endpoint_table
+                                .get("fetch_all")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false),
                         });
                     }
                 }
@@ -126,12 +284,76 @@ impl APIExecutor {
         Ok(endpoints)
     }
 
-    /// Convert TOML value to string
-    fn toml_value_to_string(value: &TomlValue) -> Option<String> {
+    /// Fail if any enabled endpoint lacks a schema definition
+    ///
+    /// A schema counts as missing when none is loaded under the endpoint's
+    /// `schema_name`, or when it has neither fields nor nested fields, as
+    /// happens when the `[name.schema]` section is absent. Every offending
+    /// endpoint is listed in one error.
+    fn check_endpoint_schemas(
+        endpoints: &[EndpointConfig],
+        schema_manager: &SchemaManager,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let missing: Vec<String> = endpoints
+            .iter()
+            .filter(|endpoint| endpoint.enabled)
+            .filter(|endpoint| {
+                schema_manager
+                    .get_schema(&endpoint.schema_name)
+                    .is_none_or(|schema| {
+                        schema.fields.is_empty() && schema.nested_fields.is_empty()
+                    })
+            })
+            .map(|endpoint| {
+                format!(
+                    "endpoint '{}' has no schema '{}' (expected a [{}.schema] section)",
+                    endpoint.name, endpoint.schema_name, endpoint.schema_name
+                )
+            })
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Missing endpoint schemas:\n  {}", missing.join("\n  ")).into())
+        }
+    }
+
+    /// Parse an optional array of strings from an endpoint table
+    fn parse_string_list(table: &toml::Table, key: &str) -> Vec<String> {
+        table
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parse an optional integer setting that must fit `T`, e.g. a retry count
+    fn parse_non_negative<T: TryFrom<i64>>(
+        value: Option<&TomlValue>,
+        name: &str,
+    ) -> Result<Option<T>, String> {
+        value
+            .and_then(|v| v.as_integer())
+            .map(|v| {
+                T::try_from(v)
+                    .map_err(|_| format!("Invalid {} {}: expected a non-negative integer", name, v))
+            })
+            .transpose()
+    }
+
+    /// Convert TOML value to string, formatting floats like schema defaults
+    fn toml_value_to_string(value: &TomlValue, float_precision: usize) -> Option<String> {
         match value {
             TomlValue::String(s) => Some(s.clone()),
             TomlValue::Integer(i) => Some(i.to_string()),
-            TomlValue::Float(f) => Some(f.to_string()),
+            TomlValue::Float(f) => Some(QueryParamValue::Float(*f).format(float_precision)),
             TomlValue::Boolean(b) => Some(b.to_string()),
             _ => None,
         }
@@ -160,57 +382,340 @@ impl APIExecutor {
                 10
             };
 
+        let max_limit = match config
+            .get("config")
+            .and_then(|v| v.get("max_limit"))
+            .and_then(|v| v.as_integer())
+        {
+            Some(v) => u32::try_from(v)
+                .ok()
+                .filter(|limit| *limit >= 1)
+                .ok_or_else(|| format!("Invalid max_limit {}: expected a positive integer", v))?,
+            None => DEFAULT_MAX_LIMIT,
+        };
+
+        let query_encoding = config
+            .get("config")
+            .and_then(|v| v.get("query_encoding"))
+            .map(|encoding| encoding.clone().try_into())
+            .transpose()
+            .map_err(|e| format!("Invalid query_encoding: {}", e))?
+            .unwrap_or_default();
+
+        let float_precision = match config
+            .get("config")
+            .and_then(|v| v.get("float_precision"))
+            .and_then(|v| v.as_integer())
+        {
+            Some(v) => usize::try_from(v).map_err(|_| {
+                format!(
+                    "Invalid float_precision {}: expected a non-negative integer",
+                    v
+                )
+            })?,
+            None => DEFAULT_FLOAT_PRECISION,
+        };
+
+        let default_retries = RetryPolicy::default();
+        let retries = Self::parse_non_negative(
+            config.get("config").and_then(|v| v.get("retries")),
+            "retries",
+        )?
+        .unwrap_or(default_retries.max_retries);
+        let retry_delay_ms = Self::parse_non_negative(
+            config.get("config").and_then(|v| v.get("retry_delay_ms")),
+            "retry_delay_ms",
+        )?
+        .unwrap_or(DEFAULT_RETRY_DELAY.as_millis() as u64);
+
+        let state_file = config
+            .get("config")
+            .and_then(|v| v.get("state_file"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        let workers = |key: &str| {
+            config
+                .get("config")
+                .and_then(|v| v.get(key))
+                .and_then(|v| v.as_integer())
+                .map_or(1, |v| v.max(1) as usize)
+        };
+
         Ok(GlobalConfig {
             output_format,
             max_display_items,
+            max_limit,
+            query_encoding,
+            float_precision,
+            retries,
+            retry_delay_ms,
+            state_file,
+            fetch_workers: workers("fetch_workers"),
+            write_workers: workers("write_workers"),
         })
     }
 
     /// Execute all enabled endpoints
+    ///
+    /// Metrics are reset at the start of each run, so `metrics()` reflects
+    /// the most recent run once this returns. With a `state_file`, the
+    /// validators are saved after every run.
     pub async fn execute_all(&self) -> Result<(), Box<dyn std::error::Error>> {
         println!("Executing API endpoints...\n");
-
-        for endpoint in &self.endpoints {
+        self.metrics.reset();
+
+        let mut executed = 0;
+        let mut skipped = 0;
+        let mut unchanged = 0;
+        // Snapshot the endpoints so toggles made during the run apply to the next one
+        let endpoints = self.endpoints.read().unwrap().clone();
+        for endpoint in &endpoints {
             if endpoint.enabled {
-                self.execute_endpoint(endpoint).await?;
+                if self.execute_endpoint(endpoint).await? {
+                    unchanged += 1;
+                }
+                executed += 1;
+            } else {
+                println!("Skipping '{}' (disabled)", endpoint.name);
+                skipped += 1;
             }
         }
 
+        println!(
+            "Executed {} endpoint(s), skipped {} disabled",
+            executed, skipped
+        );
+        if self.global_config.state_file.is_some() {
+            println!("{} endpoint(s) unchanged since last run", unchanged);
+            self.save_state()?;
+        }
+        println!("Request metrics:");
+        print!("{}", self.metrics);
+
         Ok(())
     }
 
     /// Execute a single endpoint
+    ///
+    /// Returns `true` if the request was conditional and the server
+    /// answered `304 Not Modified`.
     async fn execute_endpoint(
         &self,
         _endpoint: &EndpointConfig,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<bool, Box<dyn std::error::Error>> {
         println!("Fetching data from: {} ({})", _endpoint.name, _endpoint.url);
 
         // Extract base URL and endpoint path
         let (base_url, endpoint_path) = Self::split_url(&_endpoint.url);
 
         // Create a new client with the correct base URL for this endpoint
-        let client = RESTClient::with_schemas(&base_url, self.schema_manager.clone());
+        // Transient failures (no response, 429, 5xx) are retried by the
+        // client's default retry predicate
+        let retries = _endpoint.retries.unwrap_or(self.global_config.retries);
+        let retry_delay = Duration::from_millis(
+            _endpoint
+                .retry_delay_ms
+                .unwrap_or(self.global_config.retry_delay_ms),
+        );
+        let client = RESTClient::with_schemas(&base_url, self.schema_manager.clone())
+            .with_transport(self.transport.clone())
+            .with_observer(self.metrics.observer_for(&_endpoint.name))
+            .with_retries(retries, retry_delay);
+
+        if _endpoint.fetch_all {
+            let pages = self
+                .ingest_all(&client, &endpoint_path, _endpoint)
+                .await
+                .map_err(|e| e as Box<dyn std::error::Error>)?;
+            println!("{}: wrote {} page(s)", _endpoint.name, pages);
+            println!();
+            return Ok(false);
+        }
 
         // Execute the request
-        match client
-            .get_with_params_and_schema(
-                &endpoint_path,
-                &_endpoint.schema_name,
-                &_endpoint.query_params,
-            )
-            .await
-        {
-            Ok(data) => {
-                self.display_results(_endpoint, &data)?;
+        let result = if self.global_config.state_file.is_some() {
+            self.fetch_conditional(&client, &endpoint_path, _endpoint)
+                .await
+        } else {
+            client
+                .get_with_params_and_schema(
+                    &endpoint_path,
+                    &_endpoint.schema_name,
+                    &_endpoint.query_params,
+                )
+                .await
+                .map(Some)
+                .map_err(|e| e.into())
+        };
+
+        let unchanged = match result {
+            Ok(Some(mut data)) => {
+                Self::filter_sites(_endpoint, &mut data);
+                self.sink
+                    .write(&_endpoint.name, &data)
+                    .await
+                    .map_err(|e| e as Box<dyn std::error::Error>)?;
+                false
+            }
+            Ok(None) => {
+                println!("{}: unchanged since last run (304)", _endpoint.name);
+                true
             }
             Err(e) => {
                 eprintln!("Error fetching {}: {}", _endpoint.name, e);
+                false
             }
-        }
+        };
 
         println!(); // Add spacing between endpoints
-        Ok(())
+        Ok(unchanged)
+    }
+
+    /// Fetch every page of an endpoint and write each to the sink
+    ///
+    /// The first page, at the endpoint's `offset`, gives `count`; the rest
+    /// are then requested by offset, `fetch_workers` at a time, and passed
+    /// through a bounded channel to up to `write_workers` concurrent sink
+    /// writes. When the writers fall behind, the channel fills and
+    /// fetching waits, so only a few pages per worker are held at once.
+    /// Pages reach the sink in offset order only with one writer.
+    ///
+    /// Returns the number of pages written. A fetch error stops fetching
+    /// and is returned once the pages already queued have been written; a
+    /// write error stops both and is returned instead. Requests are never
+    /// conditional.
+    async fn ingest_all(
+        &self,
+        client: &RESTClient,
+        endpoint_path: &str,
+        endpoint: &EndpointConfig,
+    ) -> Result<usize, BoxError> {
+        let max_limit = self.global_config.max_limit.max(1);
+        let page_size = endpoint
+            .query_params
+            .get("limit")
+            .and_then(|v| v.parse::<u32>().ok())
+            .map_or(max_limit, |limit| limit.clamp(1, max_limit)) as usize;
+        let start = endpoint
+            .query_params
+            .get("offset")
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(0);
+
+        let fetch_page = move |offset: usize| async move {
+            // Paging is set here whether or not the schema declares it
+            let mut params = endpoint.query_params.clone();
+            params.remove("limit");
+            params.remove("offset");
+            let query_string = self
+                .schema_manager
+                .build_query_string(&endpoint.schema_name, &params)
+                .map_err(|e| e.to_string())?;
+            let separator = if query_string.is_empty() { '?' } else { '&' };
+            let page: Value = client
+                .get(&format!(
+                    "{}{}{}limit={}&offset={}",
+                    endpoint_path, query_string, separator, page_size, offset
+                ))
+                .await?;
+            let mut data = self
+                .schema_manager
+                .apply_schema(&endpoint.schema_name, &page)
+                .map_err(|e| e.to_string())?;
+            Self::filter_sites(endpoint, &mut data);
+            Ok::<_, BoxError>(data)
+        };
+
+        let (tx, rx) = mpsc::channel::<Value>(self.global_config.write_workers);
+        let fetch = async move {
+            let first = fetch_page(start).await?;
+            let count = first.get("count").and_then(Value::as_u64).unwrap_or(0) as usize;
+            let mut fetched = 1;
+            if tx.send(first).await.is_err() {
+                return Ok(fetched);
+            }
+
+            let mut pages = stream::iter((start + page_size..count).step_by(page_size))
+                .map(fetch_page)
+                .buffered(self.global_config.fetch_workers);
+            while let Some(page) = pages.next().await {
+                // The writers only hang up after a failed write
+                if tx.send(page?).await.is_err() {
+                    break;
+                }
+                fetched += 1;
+            }
+            Ok(fetched)
+        };
+        let write = stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|page| (Ok(page), rx))
+        })
+        .try_for_each_concurrent(self.global_config.write_workers, |page| async move {
+            self.sink.write(&endpoint.name, &page).await
+        });
+
+        let (fetched, written) = tokio::join!(fetch, write);
+        written?;
+        fetched
+    }
+
+    /// Fetch an endpoint conditionally on its saved validators
+    ///
+    /// Returns `None` if it is unchanged; otherwise applies the schema and
+    /// remembers the new validators.
+    async fn fetch_conditional(
+        &self,
+        client: &RESTClient,
+        endpoint_path: &str,
+        endpoint: &EndpointConfig,
+    ) -> Result<Option<Value>, Box<dyn std::error::Error>> {
+        let query_string = self
+            .schema_manager
+            .build_query_string(&endpoint.schema_name, &endpoint.query_params)?;
+        let path = format!("{}{}", endpoint_path, query_string);
+        let validators = self
+            .state
+            .lock()
+            .unwrap()
+            .get(&endpoint.name)
+            .cloned()
+            .unwrap_or_default();
+
+        match client.get_conditional::<Value>(&path, &validators).await? {
+            Conditional::NotModified => Ok(None),
+            Conditional::Modified { value, validators } => {
+                let data = self
+                    .schema_manager
+                    .apply_schema(&endpoint.schema_name, &value)?;
+                self.state
+                    .lock()
+                    .unwrap()
+                    .insert(endpoint.name.clone(), validators);
+                Ok(Some(data))
+            }
+        }
+    }
+
+    /// Drop results whose `news_site` fails the endpoint's site filters
+    ///
+    /// Runs before the data reaches the sink, so display limits apply to
+    /// the items that actually matched.
+    fn filter_sites(endpoint: &EndpointConfig, data: &mut Value) {
+        if endpoint.include_sites.is_empty() && endpoint.exclude_sites.is_empty() {
+            return;
+        }
+
+        let matches =
+            |sites: &[String], site: &str| sites.iter().any(|s| s.eq_ignore_ascii_case(site));
+
+        if let Some(results) = data.get_mut("results").and_then(|v| v.as_array_mut()) {
+            results.retain(|item| {
+                let site = item.get("news_site").and_then(|v| v.as_str()).unwrap_or("");
+                (endpoint.include_sites.is_empty() || matches(&endpoint.include_sites, site))
+                    && !matches(&endpoint.exclude_sites, site)
+            });
+        }
     }
 
     /// Split URL into base URL and endpoint path
@@ -223,168 +728,272 @@ impl APIExecutor {
             (url.to_string(), "".to_string())
         }
     }
+}
 
-    /// Display results based on configuration
-    fn display_results(
-        &self,
-        _endpoint: &EndpointConfig,
-        data: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        match self.global_config.output_format.as_str() {
-            "json" => {
-                println!("{}", serde_json::to_string_pretty(data)?);
-            }
-            "table" => {
-                self.display_as_table(_endpoint, data)?;
-            }
-            "detailed" | _ => {
-                self.display_detailed(_endpoint, data)?;
-            }
+impl Default for APIExecutor {
+    fn default() -> Self {
+        Self {
+            schema_manager: SchemaManager::new(),
+            endpoints: RwLock::new(Vec::new()),
+            global_config: GlobalConfig {
+                output_format: "detailed".to_string(),
+                max_display_items: 10,
+                max_limit: DEFAULT_MAX_LIMIT,
+                query_encoding: QueryEncoding::default(),
+                float_precision: DEFAULT_FLOAT_PRECISION,
+                retries: RetryPolicy::default().max_retries,
+                retry_delay_ms: DEFAULT_RETRY_DELAY.as_millis() as u64,
+                state_file: None,
+                fetch_workers: 1,
+                write_workers: 1,
+            },
+            sink: Box::new(StdoutSink::default()),
+            transport: Arc::new(HttpTransport),
+            metrics: Arc::new(Metrics::new()),
+            state: Mutex::new(HashMap::new()),
         }
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Semaphore;
+
+    fn global_config(toml: &str) -> Result<GlobalConfig, String> {
+        APIExecutor::parse_global_config(&toml::from_str(toml).unwrap()).map_err(|e| e.to_string())
     }
 
-    /// Display results in detailed format
-    fn display_detailed(
-        &self,
-        _endpoint: &EndpointConfig,
-        data: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(obj) = data.as_object() {
-            if obj.contains_key("results") && obj.contains_key("count") {
-                // Paginated response
-                if let Some(count) = obj.get("count").and_then(|v| v.as_u64()) {
-                    println!("  Total results: {}", count);
-                }
+    #[test]
+    fn max_limit_must_be_positive() {
+        assert_eq!(global_config("").unwrap().max_limit, DEFAULT_MAX_LIMIT);
+        assert_eq!(
+            global_config("[config]\nmax_limit = 1").unwrap().max_limit,
+            1
+        );
+        for value in ["0", "-1", "4294967296"] {
+            assert_eq!(
+                global_config(&format!("[config]\nmax_limit = {}", value)).unwrap_err(),
+                format!("Invalid max_limit {}: expected a positive integer", value)
+            );
+        }
+    }
 
-                if let Some(results) = obj.get("results").and_then(|v| v.as_array()) {
-                    println!(
-                        "  Displaying first {} items:",
-                        std::cmp::min(results.len(), self.global_config.max_display_items)
-                    );
-
-                    for (i, item) in results
-                        .iter()
-                        .take(self.global_config.max_display_items)
-                        .enumerate()
-                    {
-                        println!("    Item {}:", i + 1);
-                        self.display_object(item, 6)?;
-                    }
-                }
-            } else {
-                // Single object
-                println!("  Response:");
-                self.display_object(data, 4)?;
-            }
-        } else {
-            println!("  Response: {:?}", data);
+    #[test]
+    fn retry_settings_must_be_non_negative() {
+        let config = global_config("[config]\nretries = 0\nretry_delay_ms = 250").unwrap();
+        assert_eq!((config.retries, config.retry_delay_ms), (0, 250));
+        assert_eq!(
+            global_config("[config]\nretries = -1").unwrap_err(),
+            "Invalid retries -1: expected a non-negative integer"
+        );
+        assert_eq!(
+            global_config("[config]\nretries = 4294967296").unwrap_err(),
+            "Invalid retries 4294967296: expected a non-negative integer"
+        );
+        assert_eq!(
+            global_config("[config]\nretry_delay_ms = -5").unwrap_err(),
+            "Invalid retry_delay_ms -5: expected a non-negative integer"
+        );
+    }
+
+    #[test]
+    fn endpoint_retry_settings_must_be_non_negative() {
+        let endpoints = |toml: &str| {
+            APIExecutor::parse_endpoints(&toml::from_str(toml).unwrap(), DEFAULT_FLOAT_PRECISION)
+                .map_err(|e| e.to_string())
+        };
+        let parsed = endpoints(
+            "[articles]\nurl = \"https://example.com/articles\"\nretries = 2\nretry_delay_ms = 50",
+        )
+        .unwrap();
+        assert_eq!(
+            (parsed[0].retries, parsed[0].retry_delay_ms),
+            (Some(2), Some(50))
+        );
+        assert_eq!(
+            endpoints("[articles]\nurl = \"https://example.com/articles\"\nretries = -3")
+                .unwrap_err(),
+            "Invalid articles.retries -3: expected a non-negative integer"
+        );
+        assert_eq!(
+            endpoints("[articles]\nurl = \"https://example.com/articles\"\nretry_delay_ms = -1")
+                .unwrap_err(),
+            "Invalid articles.retry_delay_ms -1: expected a non-negative integer"
+        );
+    }
+
+    #[test]
+    fn endpoint_float_params_use_the_float_precision() {
+        let config: TomlValue = toml::from_str(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+
+            [articles.query_params]
+            radius = 0.30000000000000004
+            tiny = 0.0000001
+            "#,
+        )
+        .unwrap();
+
+        let endpoints = APIExecutor::parse_endpoints(&config, DEFAULT_FLOAT_PRECISION).unwrap();
+        assert_eq!(endpoints[0].query_params["radius"], "0.3");
+        assert_eq!(endpoints[0].query_params["tiny"], "0.0000001");
+
+        let endpoints = APIExecutor::parse_endpoints(&config, 0).unwrap();
+        assert_eq!(endpoints[0].query_params["radius"], "0.3");
+        assert_eq!(
+            global_config("[config]\nfloat_precision = 2")
+                .unwrap()
+                .float_precision,
+            2
+        );
+    }
+
+    /// Transport serving `count` one-item pages by offset, slower for
+    /// earlier pages, and failing at `fail_at`
+    struct Pages {
+        count: u64,
+        fail_at: Option<u64>,
+        calls: AtomicUsize,
+    }
+
+    impl Pages {
+        fn new(count: u64, fail_at: Option<u64>) -> Arc<Self> {
+            Arc::new(Self {
+                count,
+                fail_at,
+                calls: Default::default(),
+            })
         }
 
-        Ok(())
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
     }
 
-    /// Display a JSON object with indentation
-    fn display_object(
-        &self,
-        value: &Value,
-        indent: usize,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let indent_str = " ".repeat(indent);
-
-        if let Some(obj) = value.as_object() {
-            for (key, val) in obj {
-                match val {
-                    Value::Object(_) => {
-                        println!("{}{}:", indent_str, key);
-                        self.display_object(val, indent + 2)?;
-                    }
-                    Value::Array(arr) => {
-                        println!("{}{}: [{} items]", indent_str, key, arr.len());
-                        if !arr.is_empty() && key != "events" && key != "launches" {
-                            if let Some(first) = arr.first() {
-                                if first.is_object() {
-                                    println!("{}  First item:", indent_str);
-                                    self.display_object(first, indent + 4)?;
-                                } else {
-                                    println!("{}  First item: {:?}", indent_str, first);
-                                }
-                            }
-                        }
-                    }
-                    _ => {
-                        println!("{}{}: {}", indent_str, key, val);
-                    }
-                }
+    #[async_trait]
+    impl Transport for Pages {
+        async fn execute(
+            &self,
+            _: &reqwest::Client,
+            request: reqwest::Request,
+        ) -> Result<reqwest::Response, crate::ClientError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let offset: u64 = request
+                .url()
+                .query_pairs()
+                .find(|(name, _)| name == "offset")
+                .map_or(0, |(_, value)| value.parse().unwrap());
+            // Later pages finish first when fetched concurrently
+            for _ in offset..self.count {
+                tokio::task::yield_now().await;
             }
-        } else {
-            println!("{}{:?}", indent_str, value);
+            let response = if self.fail_at == Some(offset) {
+                http::Response::builder().status(500).body(String::new())
+            } else {
+                let page = serde_json::json!({
+                    "count": self.count,
+                    "next": null,
+                    "previous": null,
+                    "results": [{ "id": offset }],
+                });
+                http::Response::builder().body(page.to_string())
+            };
+            Ok(reqwest::Response::from(response.unwrap()))
         }
+    }
 
-        Ok(())
+    /// Sink recording the IDs of the pages written, each write waiting for
+    /// a permit from `permits`
+    struct Written {
+        ids: Mutex<Vec<u64>>,
+        permits: Arc<Semaphore>,
     }
 
-    /// Display results in table format
-    fn display_as_table(
-        &self,
-        _endpoint: &EndpointConfig,
-        data: &Value,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(obj) = data.as_object() {
-            if let Some(results) = obj.get("results").and_then(|v| v.as_array()) {
-                println!(
-                    "  | {:<30} | {:<20} | {:<20} |",
-                    "Title", "News Site", "Published"
-                );
-                println!("  |{:-<32}|{:-<22}|{:-<22}|", "", "", "");
-
-                for item in results.iter().take(self.global_config.max_display_items) {
-                    if let Some(item_obj) = item.as_object() {
-                        let title = item_obj
-                            .get("title")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("N/A");
-                        let news_site = item_obj
-                            .get("news_site")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("N/A");
-                        let published = item_obj
-                            .get("published_at")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("N/A");
-
-                        // Truncate long titles
-                        let title_truncated = if title.len() > 27 {
-                            format!("{}...", &title[..27])
-                        } else {
-                            title.to_string()
-                        };
-
-                        println!(
-                            "  | {:<30} | {:<20} | {:<20} |",
-                            title_truncated,
-                            news_site,
-                            &published[..std::cmp::min(20, published.len())]
-                        );
-                    }
-                }
+    #[async_trait]
+    impl ContentSink for Arc<Written> {
+        async fn write(&self, _: &str, data: &Value) -> Result<(), BoxError> {
+            self.permits.acquire().await?.forget();
+            for item in data["results"].as_array().unwrap() {
+                self.ids.lock().unwrap().push(item["id"].as_u64().unwrap());
             }
+            Ok(())
         }
+    }
 
-        Ok(())
+    /// An executor fetching all pages of `transport`'s endpoint one item
+    /// at a time into `sink`
+    fn paging_executor(
+        fetch_workers: usize,
+        transport: &Arc<Pages>,
+        permits: usize,
+    ) -> (APIExecutor, Arc<Written>) {
+        let config = format!(
+            r#"
+            [config]
+            max_limit = 1
+            retries = 0
+            fetch_workers = {fetch_workers}
+            write_workers = 1
+
+            [items]
+            url = "http://api.test/items"
+            enabled = true
+            fetch_all = true
+
+            [items.schema]
+            id = "u32"
+            "#
+        );
+        let sink = Arc::new(Written {
+            ids: Mutex::new(Vec::new()),
+            permits: Arc::new(Semaphore::new(permits)),
+        });
+        let executor = APIExecutor::from_config_str(&config)
+            .unwrap()
+            .with_transport(transport.clone())
+            .with_sink(Box::new(sink.clone()));
+        (executor, sink)
     }
-}
 
-impl Default for APIExecutor {
-    fn default() -> Self {
-        Self {
-            schema_manager: SchemaManager::new(),
-            endpoints: Vec::new(),
-            global_config: GlobalConfig {
-                output_format: "detailed".to_string(),
-                max_display_items: 10,
-            },
-        }
+    #[tokio::test]
+    async fn fetch_all_writes_pages_in_offset_order() {
+        let transport = Pages::new(8, None);
+        let (executor, sink) = paging_executor(4, &transport, Semaphore::MAX_PERMITS);
+        executor.execute_all().await.unwrap();
+        assert_eq!(*sink.ids.lock().unwrap(), (0..8).collect::<Vec<_>>());
+        assert_eq!(transport.calls(), 8);
+    }
+
+    #[tokio::test]
+    async fn fetching_waits_for_slow_writers() {
+        let transport = Pages::new(10, None);
+        let (executor, sink) = paging_executor(1, &transport, 0);
+        let check = async {
+            for _ in 0..200 {
+                tokio::task::yield_now().await;
+            }
+            // One page being written, one queued and one waiting to be queued
+            assert_eq!(transport.calls(), 3);
+            assert!(sink.ids.lock().unwrap().is_empty());
+            sink.permits.add_permits(10);
+        };
+        let (result, ()) = tokio::join!(executor.execute_all(), check);
+        result.unwrap();
+        assert_eq!(transport.calls(), 10);
+        assert_eq!(*sink.ids.lock().unwrap(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn fetch_errors_are_returned_after_queued_pages_are_written() {
+        let transport = Pages::new(6, Some(3));
+        let (executor, sink) = paging_executor(1, &transport, Semaphore::MAX_PERMITS);
+        let error = executor.execute_all().await.unwrap_err();
+        assert!(error.to_string().contains("500"), "{}", error);
+        assert_eq!(*sink.ids.lock().unwrap(), [0, 1, 2]);
     }
 }
@@ -0,0 +1,281 @@
+//! RSS and sitemap rendering of fetched articles
+//!
+//! [`to_rss`] turns a list of articles into an RSS 2.0 document, for
+//! re-publishing a filtered selection as a feed; [`to_sitemap`] lists
+//! their URLs for search engines.
+
+use crate::endpoints::article::Article;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+
+/// Channel-level details of a feed
+#[derive(Debug, Clone)]
+pub struct FeedMeta {
+    /// Feed title
+    pub title: String,
+    /// URL of the site the feed belongs to
+    pub link: String,
+    /// What the feed contains
+    pub description: String,
+}
+
+/// Render `items` as an RSS 2.0 document
+///
+/// Each article becomes an `<item>` with its title, `url` as link and
+/// permalink GUID, `summary` as description, and `published_at` as an
+/// RFC 822 `pubDate` (omitted if it doesn't parse). Authors are listed as
+/// `dc:creator` elements, since RSS's own `author` expects an email
+/// address. Items keep the order they are given in.
+pub fn to_rss(items: &[Article], channel: FeedMeta) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+    xml.push_str("<channel>\n");
+    element(&mut xml, 1, "title", &channel.title);
+    element(&mut xml, 1, "link", &channel.link);
+    element(&mut xml, 1, "description", &channel.description);
+
+    for article in items {
+        xml.push_str("  <item>\n");
+        element(&mut xml, 2, "title", &article.title);
+        element(&mut xml, 2, "link", &article.url);
+        let _ = writeln!(
+            xml,
+            "    <guid isPermaLink=\"true\">{}</guid>",
+            escape(&article.url)
+        );
+        if let Some(summary) = &article.summary {
+            element(&mut xml, 2, "description", summary);
+        }
+        if let Ok(published_at) = DateTime::parse_from_rfc3339(&article.published_at) {
+            element(&mut xml, 2, "pubDate", &published_at.to_rfc2822());
+        }
+        for author in &article.authors {
+            element(&mut xml, 2, "dc:creator", &author.name);
+        }
+        xml.push_str("  </item>\n");
+    }
+
+    xml.push_str("</channel>\n");
+    xml.push_str("</rss>\n");
+    xml
+}
+
+/// Render the URLs of `items` as a sitemap `<urlset>`
+///
+/// Each article becomes a `<url>` with its `url` as `<loc>` and
+/// `updated_at` as `<lastmod>` in W3C datetime format, in UTC (omitted if
+/// it doesn't parse). URLs without a scheme are taken as relative to
+/// `base` and joined with a single `/`. Articles with an empty URL are
+/// skipped. Items keep the order they are given in.
+pub fn to_sitemap(items: &[Article], base: &str) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+
+    for article in items {
+        let url = article.url.trim();
+        if url.is_empty() {
+            continue;
+        }
+        let loc = if url.contains("://") {
+            url.to_string()
+        } else {
+            format!(
+                "{}/{}",
+                base.trim_end_matches('/'),
+                url.trim_start_matches('/')
+            )
+        };
+
+        xml.push_str("  <url>\n");
+        element(&mut xml, 2, "loc", &loc);
+        if let Ok(updated_at) = DateTime::parse_from_rfc3339(&article.updated_at) {
+            let lastmod = updated_at
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true);
+            element(&mut xml, 2, "lastmod", &lastmod);
+        }
+        xml.push_str("  </url>\n");
+    }
+
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Append `<name>text</name>` on its own line, indented by `depth` levels
+fn element(xml: &mut String, depth: usize, name: &str, text: &str) {
+    let _ = writeln!(
+        xml,
+        "{}<{}>{}</{}>",
+        "  ".repeat(depth),
+        name,
+        escape(text),
+        name
+    );
+}
+
+/// Escape text for XML content or attribute values
+///
+/// Control characters XML 1.0 does not allow are dropped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn article(fields: serde_json::Value) -> Article {
+        let mut article = json!({
+            "id": 1,
+            "title": "Launch recap",
+            "url": "https://example.com/recap",
+            "news_site": "Example News",
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T12:00:00Z",
+        });
+        article
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(article).unwrap()
+    }
+
+    fn channel() -> FeedMeta {
+        FeedMeta {
+            title: "Launches".to_string(),
+            link: "https://example.com".to_string(),
+            description: "Selected launch news".to_string(),
+        }
+    }
+
+    #[test]
+    fn rss_escapes_titles_and_summaries() {
+        let rss = to_rss(
+            &[article(json!({
+                "title": "Tom & Jerry's <\"launch\">",
+                "summary": "a < b && c > \"d\" 'e'",
+            }))],
+            channel(),
+        );
+        assert!(rss.contains("<title>Tom &amp; Jerry&apos;s &lt;&quot;launch&quot;&gt;</title>"));
+        assert!(rss.contains(
+            "<description>a &lt; b &amp;&amp; c &gt; &quot;d&quot; &apos;e&apos;</description>"
+        ));
+    }
+
+    #[test]
+    fn rss_dates_are_rfc_822() {
+        let rss = to_rss(
+            &[article(
+                json!({ "published_at": "2026-10-01T14:30:00+02:00" }),
+            )],
+            channel(),
+        );
+        assert!(rss.contains("<pubDate>Thu, 1 Oct 2026 14:30:00 +0200</pubDate>"));
+    }
+
+    #[test]
+    fn rss_omits_unparseable_dates() {
+        let rss = to_rss(
+            &[article(json!({ "published_at": "last week" }))],
+            channel(),
+        );
+        assert!(!rss.contains("<pubDate>"));
+        assert!(rss.contains("<title>Launch recap</title>"));
+    }
+
+    #[test]
+    fn rss_lists_authors_as_dc_creator() {
+        let rss = to_rss(
+            &[article(json!({
+                "authors": [
+                    { "name": "Ada", "socials": null },
+                    { "name": "Grace & co", "socials": null },
+                ],
+            }))],
+            channel(),
+        );
+        assert!(
+            rss.contains(
+                "<dc:creator>Ada</dc:creator>\n    <dc:creator>Grace &amp; co</dc:creator>"
+            )
+        );
+        assert!(!rss.contains("<author>"));
+    }
+
+    #[test]
+    fn sitemap_skips_empty_urls() {
+        let sitemap = to_sitemap(
+            &[
+                article(json!({ "url": "  " })),
+                article(json!({ "url": "https://example.com/a" })),
+            ],
+            "https://example.com",
+        );
+        assert_eq!(sitemap.matches("<url>").count(), 1);
+        assert!(sitemap.contains("<loc>https://example.com/a</loc>"));
+    }
+
+    #[test]
+    fn sitemap_joins_relative_urls_with_one_slash() {
+        for (base, url) in [
+            ("https://example.com", "news/a"),
+            ("https://example.com/", "news/a"),
+            ("https://example.com/", "/news/a"),
+            ("https://example.com//", "//news/a"),
+        ] {
+            let sitemap = to_sitemap(&[article(json!({ "url": url }))], base);
+            assert!(
+                sitemap.contains("<loc>https://example.com/news/a</loc>"),
+                "{base} + {url}: {sitemap}"
+            );
+        }
+        let sitemap = to_sitemap(
+            &[article(json!({ "url": "https://other.test/x" }))],
+            "https://example.com",
+        );
+        assert!(sitemap.contains("<loc>https://other.test/x</loc>"));
+    }
+
+    #[test]
+    fn sitemap_lastmod_is_utc() {
+        let sitemap = to_sitemap(
+            &[article(
+                json!({ "updated_at": "2026-10-01T01:30:00+02:00" }),
+            )],
+            "https://example.com",
+        );
+        assert!(sitemap.contains("<lastmod>2026-09-30T23:30:00Z</lastmod>"));
+
+        let sitemap = to_sitemap(
+            &[article(json!({ "updated_at": "soon" }))],
+            "https://example.com",
+        );
+        assert!(!sitemap.contains("<lastmod>"));
+    }
+
+    #[test]
+    fn sitemap_escapes_loc() {
+        let sitemap = to_sitemap(
+            &[article(json!({ "url": "https://example.com/?a=1&b=<2>" }))],
+            "https://example.com",
+        );
+        assert!(sitemap.contains("<loc>https://example.com/?a=1&amp;b=&lt;2&gt;</loc>"));
+    }
+}
@@ -0,0 +1,182 @@
+//! In-memory full-text search over fetched articles
+//!
+//! [`ContentIndex`] builds an inverted index over the titles and summaries
+//! of a slice of articles, for searching a downloaded dataset without a
+//! database.
+
+use crate::endpoints::article::Article;
+use std::collections::{HashMap, HashSet};
+
+/// Inverted index over the title and summary words of some articles
+#[derive(Debug, Clone)]
+pub struct ContentIndex<'a> {
+    /// The indexed articles, in the order given
+    articles: &'a [Article],
+    /// Each word and the articles containing it, as (position in
+    /// `articles`, occurrences) in article order
+    postings: HashMap<String, Vec<(usize, u32)>>,
+}
+
+impl<'a> ContentIndex<'a> {
+    /// Index the title and summary of each article
+    pub fn new(articles: &'a [Article]) -> Self {
+        let mut postings: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+        for (position, article) in articles.iter().enumerate() {
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            let summary = article.summary.as_deref().unwrap_or_default();
+            for word in tokenize(&article.title).chain(tokenize(summary)) {
+                *counts.entry(word).or_default() += 1;
+            }
+            for (word, count) in counts {
+                postings.entry(word).or_default().push((position, count));
+            }
+        }
+        Self { articles, postings }
+    }
+
+    /// Articles matching `query`, most relevant first
+    ///
+    /// Query words are split and lowercased like the indexed text. All
+    /// words must appear unless separated by `OR`, which matches either
+    /// side: `mars rover OR lander` finds articles with both `mars` and
+    /// `rover`, or with `lander`. Matches are ranked by how often the
+    /// query's words occur in them; ties keep their indexing order. An
+    /// empty query matches nothing.
+    pub fn search(&self, query: &str) -> Vec<&'a Article> {
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let mut matched: HashSet<usize> = HashSet::new();
+        let mut terms: HashSet<String> = HashSet::new();
+
+        for group in words.split(|word| *word == "OR") {
+            let group_terms: HashSet<String> =
+                group.iter().flat_map(|word| tokenize(word)).collect();
+            let mut group_matches: Option<HashSet<usize>> = None;
+            for term in &group_terms {
+                let hits: HashSet<usize> = self
+                    .postings(term)
+                    .iter()
+                    .map(|&(position, _)| position)
+                    .collect();
+                group_matches = Some(match group_matches {
+                    None => hits,
+                    Some(so_far) => so_far.intersection(&hits).copied().collect(),
+                });
+            }
+            matched.extend(group_matches.unwrap_or_default());
+            terms.extend(group_terms);
+        }
+
+        let mut scores: HashMap<usize, u32> = HashMap::new();
+        for term in &terms {
+            for &(position, count) in self.postings(term) {
+                if matched.contains(&position) {
+                    *scores.entry(position).or_default() += count;
+                }
+            }
+        }
+
+        let mut ranked: Vec<(usize, u32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .map(|(position, _)| &self.articles[position])
+            .collect()
+    }
+
+    /// Articles containing `term`, with its occurrences in each
+    fn postings(&self, term: &str) -> &[(usize, u32)] {
+        self.postings.get(term).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Split text into lowercase words at every character that is not a
+/// letter or digit, in any script
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn article(id: u32, title: &str, summary: &str) -> Article {
+        serde_json::from_value(json!({
+            "id": id,
+            "title": title,
+            "url": format!("https://example.com/{id}"),
+            "news_site": "Example News",
+            "summary": summary,
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T12:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn ids(found: Vec<&Article>) -> Vec<u32> {
+        found.iter().map(|article| article.id).collect()
+    }
+
+    fn articles() -> Vec<Article> {
+        vec![
+            article(1, "Mars rover lands", "The rover touched down on Mars."),
+            article(2, "Lunar lander delayed", "The lander slips a month."),
+            article(3, "Rover tests", "Earth-bound rover trials continue."),
+            article(4, "Mars sample return", "Mars, Mars and more Mars."),
+        ]
+    }
+
+    #[test]
+    fn all_words_of_a_group_must_match() {
+        let articles = articles();
+        let index = ContentIndex::new(&articles);
+        assert_eq!(ids(index.search("mars rover")), [1]);
+        assert_eq!(ids(index.search("MARS Rover!")), [1]);
+        assert!(index.search("mars lander").is_empty());
+    }
+
+    #[test]
+    fn or_matches_either_group() {
+        let articles = articles();
+        let index = ContentIndex::new(&articles);
+        assert_eq!(ids(index.search("mars rover OR lander")), [1, 2]);
+        // Ranked by occurrences whichever group matched
+        assert_eq!(ids(index.search("sample OR lander")), [2, 4]);
+        // Lowercase `or` is an ordinary word
+        assert!(index.search("lander or sample").is_empty());
+    }
+
+    #[test]
+    fn ranks_by_occurrences_and_keeps_ties_in_order() {
+        let articles = articles();
+        let index = ContentIndex::new(&articles);
+        // Article 4 mentions Mars five times, article 1 twice
+        assert_eq!(ids(index.search("mars")), [4, 1]);
+        // Articles 1 and 3 both mention rover twice
+        assert_eq!(ids(index.search("rover")), [1, 3]);
+    }
+
+    #[test]
+    fn tokenizes_unicode_words() {
+        let articles = vec![
+            article(1, "Запуск «Союза»", "Старт с Байконура"),
+            article(2, "Ariane 6 décolle", "Lancement réussi à Kourou"),
+        ];
+        let index = ContentIndex::new(&articles);
+        assert_eq!(ids(index.search("союза")), [1]);
+        assert_eq!(ids(index.search("DÉCOLLE")), [2]);
+        assert_eq!(ids(index.search("ariane 6")), [2]);
+    }
+
+    #[test]
+    fn empty_queries_match_nothing() {
+        let articles = articles();
+        let index = ContentIndex::new(&articles);
+        assert!(index.search("").is_empty());
+        assert!(index.search("   ").is_empty());
+        assert!(index.search("OR").is_empty());
+        assert!(index.search("?!").is_empty());
+    }
+}
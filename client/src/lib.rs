@@ -1,11 +1,40 @@
 pub mod client;
 pub mod endpoints;
+pub mod error;
 pub mod executor;
+pub mod feed;
+pub mod index;
+pub mod metrics;
 pub mod schema;
+pub mod sink;
 pub mod utils;
 
+pub use client::api::SpaceflightApi;
+pub use client::auth::Auth;
+pub use client::clock::{Clock, MockClock, SystemClock};
+pub use client::conditional::{Conditional, Validators};
+pub use client::observer::{RequestEvent, RequestObserver};
+pub use client::pagination::PaginationStyle;
+pub use client::query::{ArticleQuery, ContentQuery, LaunchQuery};
+pub use client::rate_limit::RateLimiter;
 pub use client::rest_client::PaginatedResponse;
-pub use client::rest_client::RESTClient;
+pub use client::rest_client::{DEFAULT_BLOCKING_DECODE_THRESHOLD, RESTClient};
+pub use client::rest_client::{GetAllOptions, ProgressCallback};
+pub use client::rest_client::{ItemError, LenientPage, Partial, Ranged};
+pub use client::retry::{
+    Backoff, DEFAULT_RETRY_DELAY, ResponseInfo, RetryPolicy, default_retry_predicate,
+};
+pub use client::search::{Filter, FilterOp, SearchFilter};
+pub use client::spacedevs_client::{NEWS_SITE_COUNT_CONCURRENCY, SpaceDevsClient};
+pub use client::transport::{HttpTransport, Transport};
+#[cfg(feature = "vcr")]
+pub use client::vcr::{Interaction, RecordingTransport, VcrMode};
 pub use endpoints::*;
+pub use error::{ApiErrorBody, ClientError};
 pub use executor::*;
+pub use feed::{FeedMeta, to_rss, to_sitemap};
+pub use index::ContentIndex;
+pub use metrics::*;
 pub use schema::*;
+pub use sink::*;
+pub use tokio_util::sync::CancellationToken;
@@ -8,6 +8,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create executor from TOML configuration
     let executor = APIExecutor::from_config_file("simple.toml")?;
 
+    // Serve requests from a cassette when one is configured, so the example
+    // can run without live API access
+    #[cfg(feature = "vcr")]
+    let executor = match std::env::var("SPACEDEVS_CASSETTE") {
+        Ok(path) => {
+            let transport = match std::env::var("SPACEDEVS_VCR_MODE").as_deref() {
+                Ok("record") => client::RecordingTransport::record(path),
+                _ => client::RecordingTransport::replay(path)?,
+            };
+            executor.with_transport(std::sync::Arc::new(transport))
+        }
+        Err(_) => executor,
+    };
+
     // Execute all enabled endpoints
     executor.execute_all().await?;
 
@@ -0,0 +1,214 @@
+//! Per-endpoint request metrics
+//!
+//! [`Metrics`] aggregates request count, bytes and latency for each
+//! endpoint. Attach it to a client through [`Metrics::observer_for`], or let
+//! the executor do it and read the totals after `execute_all`.
+
+use crate::client::observer::{RequestEvent, RequestObserver};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Aggregated figures for one endpoint
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointMetrics {
+    /// Number of requests made
+    pub requests: u64,
+    /// Number of requests that failed
+    pub failures: u64,
+    /// Total response body bytes received
+    pub total_bytes: u64,
+    /// Fastest request
+    pub min_latency: Duration,
+    /// Slowest request
+    pub max_latency: Duration,
+    /// Sum of all request latencies
+    pub total_latency: Duration,
+}
+
+impl EndpointMetrics {
+    /// Average latency across all requests
+    ///
+    /// Divides in nanoseconds, so request counts beyond `u32::MAX` are
+    /// not truncated.
+    pub fn avg_latency(&self) -> Duration {
+        if self.requests == 0 {
+            return Duration::ZERO;
+        }
+        let nanos = self.total_latency.as_nanos() / u128::from(self.requests);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+
+    /// Fold a completed request into the totals
+    fn record(&mut self, event: &RequestEvent) {
+        if self.requests == 0 || event.latency < self.min_latency {
+            self.min_latency = event.latency;
+        }
+        self.max_latency = self.max_latency.max(event.latency);
+        self.total_latency += event.latency;
+        self.total_bytes += event.bytes;
+        self.requests += 1;
+        if !event.success {
+            self.failures += 1;
+        }
+    }
+}
+
+/// Thread-safe collector of per-endpoint metrics
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Metrics by endpoint name
+    endpoints: Mutex<BTreeMap<String, EndpointMetrics>>,
+}
+
+impl Metrics {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a completed request under `endpoint`
+    pub fn record(&self, endpoint: &str, event: &RequestEvent) {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(endpoint.to_string())
+            .or_default()
+            .record(event);
+    }
+
+    /// Get an observer that records every request under `endpoint`
+    pub fn observer_for(self: &Arc<Self>, endpoint: impl Into<String>) -> Arc<dyn RequestObserver> {
+        Arc::new(EndpointObserver {
+            endpoint: endpoint.into(),
+            metrics: Arc::clone(self),
+        })
+    }
+
+    /// Copy of the current metrics, ordered by endpoint name
+    pub fn snapshot(&self) -> BTreeMap<String, EndpointMetrics> {
+        self.endpoints.lock().unwrap().clone()
+    }
+
+    /// Metrics for a single endpoint
+    pub fn get(&self, endpoint: &str) -> Option<EndpointMetrics> {
+        self.endpoints.lock().unwrap().get(endpoint).cloned()
+    }
+
+    /// Discard everything recorded so far
+    pub fn reset(&self) {
+        self.endpoints.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "  | {:<20} | {:>8} | {:>12} | {:>10} | {:>10} | {:>10} |",
+            "Endpoint", "Requests", "Bytes", "Min", "Avg", "Max"
+        )?;
+        writeln!(
+            f,
+            "  |{:-<22}|{:-<10}|{:-<14}|{:-<12}|{:-<12}|{:-<12}|",
+            "", "", "", "", "", ""
+        )?;
+        for (name, metrics) in self.snapshot() {
+            writeln!(
+                f,
+                "  | {:<20} | {:>8} | {:>12} | {:>10.1?} | {:>10.1?} | {:>10.1?} |",
+                name,
+                metrics.requests,
+                metrics.total_bytes,
+                metrics.min_latency,
+                metrics.avg_latency(),
+                metrics.max_latency
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Observer that files events under a fixed endpoint name
+struct EndpointObserver {
+    endpoint: String,
+    metrics: Arc<Metrics>,
+}
+
+impl RequestObserver for EndpointObserver {
+    fn on_request(&self, event: &RequestEvent) {
+        self.metrics.record(&self.endpoint, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(latency_ms: u64, bytes: u64, success: bool) -> RequestEvent {
+        RequestEvent {
+            url: "https://api.test/articles/".to_string(),
+            status: Some(if success { 200 } else { 503 }),
+            bytes,
+            latency: Duration::from_millis(latency_ms),
+            success,
+        }
+    }
+
+    #[test]
+    fn aggregates_per_endpoint() {
+        let metrics = Metrics::new();
+        metrics.record("articles", &event(30, 100, true));
+        metrics.record("articles", &event(10, 50, false));
+        metrics.record("articles", &event(20, 25, true));
+        metrics.record("blogs", &event(5, 1, true));
+
+        let articles = metrics.get("articles").unwrap();
+        assert_eq!(articles.requests, 3);
+        assert_eq!(articles.failures, 1);
+        assert_eq!(articles.total_bytes, 175);
+        assert_eq!(articles.min_latency, Duration::from_millis(10));
+        assert_eq!(articles.max_latency, Duration::from_millis(30));
+        assert_eq!(articles.avg_latency(), Duration::from_millis(20));
+        assert_eq!(metrics.get("blogs").unwrap().requests, 1);
+        assert_eq!(
+            metrics.snapshot().keys().collect::<Vec<_>>(),
+            ["articles", "blogs"]
+        );
+    }
+
+    #[test]
+    fn observers_record_under_their_endpoint() {
+        let metrics = Arc::new(Metrics::new());
+        metrics
+            .observer_for("reports")
+            .on_request(&event(7, 3, true));
+        assert_eq!(metrics.get("reports").unwrap().total_bytes, 3);
+    }
+
+    #[test]
+    fn average_handles_counts_beyond_u32() {
+        assert_eq!(EndpointMetrics::default().avg_latency(), Duration::ZERO);
+        let metrics = EndpointMetrics {
+            requests: u64::from(u32::MAX) + 1,
+            total_latency: Duration::from_secs(u64::from(u32::MAX) + 1),
+            ..Default::default()
+        };
+        assert_eq!(metrics.avg_latency(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn reset_discards_everything() {
+        let metrics = Metrics::new();
+        metrics.record("articles", &event(10, 1, true));
+        metrics.reset();
+        assert!(metrics.snapshot().is_empty());
+        assert_eq!(metrics.get("articles"), None);
+
+        metrics.record("articles", &event(40, 1, true));
+        let articles = metrics.get("articles").unwrap();
+        assert_eq!(articles.requests, 1);
+        assert_eq!(articles.min_latency, Duration::from_millis(40));
+    }
+}
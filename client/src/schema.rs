@@ -3,10 +3,12 @@
 //! This module provides functionality to load struct definitions from TOML files
 //! and use them to dynamically process API responses and build query parameters.
 
+use crate::utils::urls;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
+use std::sync::Arc;
 use urlencoding;
 
 /// Represents a field in a struct definition
@@ -34,6 +36,47 @@ pub struct QueryParamDefinition {
     /// Description of the parameter
     #[serde(default)]
     pub description: Option<String>,
+    /// How array values are written to the query string
+    #[serde(default)]
+    pub array_format: ArrayFormat,
+}
+
+/// How an array-valued query parameter is written to a URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArrayFormat {
+    /// One comma-separated value, e.g. `news_site__in=NASA,SpaceNews`
+    #[default]
+    Comma,
+    /// The key repeated once per value, e.g. `news_site=NASA&news_site=SpaceNews`
+    Repeat,
+}
+
+/// How query parameter values are escaped
+///
+/// Either way only `A-Z a-z 0-9 - . _ ~` are sent as-is, so a leading `-`
+/// as in `ordering=-published_at` is kept while `&` and `=` inside a value
+/// become `%26` and `%3D`. The two differ only in spaces, which matters
+/// to servers that decode `+` as a literal plus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryEncoding {
+    /// RFC 3986 percent-encoding: a space is `%20`, `+` is `%2B`
+    #[default]
+    Percent,
+    /// HTML form encoding: a space is `+`, `+` is `%2B`
+    Form,
+}
+
+impl QueryEncoding {
+    /// Escape a query key or value
+    pub(crate) fn escape(self, value: &str) -> String {
+        let encoded = urlencoding::encode(value);
+        match self {
+            QueryEncoding::Percent => encoded.into_owned(),
+            QueryEncoding::Form => encoded.replace("%20", "+"),
+        }
+    }
 }
 
 /// Possible values for query parameters
@@ -44,16 +87,107 @@ pub enum QueryParamValue {
     Integer(i64),
     Float(f64),
     Boolean(bool),
+    Array(Vec<QueryParamValue>),
 }
 
+/// Default number of decimal places kept when formatting float query values
+pub const DEFAULT_FLOAT_PRECISION: usize = 6;
+
 impl QueryParamValue {
-    /// Convert to string representation for URL encoding
-    pub fn to_string(&self) -> String {
+    /// Format for a query string, rounding floats to `float_precision`
+    /// decimal places and dropping trailing zeros (see `format_float`)
+    pub fn format(&self, float_precision: usize) -> String {
+        match self {
+            QueryParamValue::Float(v) => format_float(*v, float_precision),
+            QueryParamValue::Array(items) => items
+                .iter()
+                .map(|item| item.format(float_precision))
+                .collect::<Vec<_>>()
+                .join(","),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Format a float without binary rounding artifacts like `0.30000000000000004`
+///
+/// Rounds to `precision` decimal places and drops trailing zeros. A nonzero
+/// value that would round to zero instead keeps `precision` significant
+/// digits (at least one), so `1e-7` is `0.0000001` rather than `0`.
+fn format_float(value: f64, precision: usize) -> String {
+    let mut formatted = format!("{:.*}", precision, value);
+    let rounded_to_zero = formatted
+        .trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.');
+    if rounded_to_zero && value != 0.0 && value.is_finite() {
+        let magnitude = value.abs().log10().floor() as i64;
+        let decimals = (precision.max(1) as i64 - 1 - magnitude).max(0) as usize;
+        formatted = format!("{:.*}", decimals, value);
+    }
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// String representation for URL encoding
+impl std::fmt::Display for QueryParamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            QueryParamValue::String(s) => s.clone(),
-            QueryParamValue::Integer(i) => i.to_string(),
-            QueryParamValue::Float(f) => f.to_string(),
-            QueryParamValue::Boolean(b) => b.to_string(),
+            QueryParamValue::String(s) => write!(f, "{}", s),
+            QueryParamValue::Integer(i) => write!(f, "{}", i),
+            QueryParamValue::Float(v) => write!(f, "{}", format_float(*v, DEFAULT_FLOAT_PRECISION)),
+            QueryParamValue::Boolean(b) => write!(f, "{}", b),
+            QueryParamValue::Array(_) => write!(f, "{}", self.format(DEFAULT_FLOAT_PRECISION)),
+        }
+    }
+}
+
+/// Operation used to derive a computed field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComputeOp {
+    /// Host part of a URL string
+    Host,
+    /// Character count of a string, or element count of an array
+    Length,
+    /// Number of whitespace-separated words in a string
+    Words,
+    /// Lowercased copy of a string
+    Lowercase,
+}
+
+/// A field that `apply_schema` derives from another field of the same item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComputedField {
+    /// Source field name
+    pub from: String,
+    /// Transform applied to the source value
+    pub op: ComputeOp,
+}
+
+impl ComputedField {
+    /// Compute the value from an item, or `Null` if the source is missing
+    /// or has the wrong type
+    pub fn compute(&self, item: &serde_json::Map<String, Value>) -> Value {
+        let source = item.get(&self.from);
+        match (self.op, source) {
+            (ComputeOp::Host, Some(Value::String(url))) => {
+                urls::host(url).map_or(Value::Null, Value::from)
+            }
+            (ComputeOp::Length, Some(Value::String(s))) => Value::from(s.chars().count()),
+            (ComputeOp::Length, Some(Value::Array(items))) => Value::from(items.len()),
+            (ComputeOp::Words, Some(Value::String(s))) => Value::from(s.split_whitespace().count()),
+            (ComputeOp::Lowercase, Some(Value::String(s))) => Value::from(s.to_lowercase()),
+            _ => Value::Null,
         }
     }
 }
@@ -71,21 +205,416 @@ pub struct Schema {
     /// Supported query parameters
     #[serde(default)]
     pub query_params: HashMap<String, QueryParamDefinition>,
+    /// Fields derived from other fields when the schema is applied
+    #[serde(default)]
+    pub computed_fields: HashMap<String, ComputedField>,
+    /// Path to a JSON Schema document describing responses, used by
+    /// `SchemaManager::validate`
+    #[serde(default)]
+    pub json_schema: Option<String>,
+    /// What `SchemaManager::apply_schema` does with undeclared fields
+    #[serde(default)]
+    pub unknown_fields: UnknownFieldPolicy,
+}
+
+/// Handling of item fields a schema does not declare
+///
+/// Declared fields are those in `fields`, `nested_fields` and
+/// `computed_fields`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownFieldPolicy {
+    /// Pass undeclared fields through unchanged
+    #[default]
+    Keep,
+    /// Remove undeclared fields, leaving only the declared shape
+    Drop,
+    /// Fail when an item has an undeclared field
+    Error,
+}
+
+impl Schema {
+    /// Undo `SchemaManager::apply_schema`, giving the shape the API sent
+    ///
+    /// Removes computed fields from each item (or from `results` of a
+    /// paginated response). A computed field that shares its name with a
+    /// declared field replaced the API's value and is left in place, since
+    /// the original is gone; fields dropped by `UnknownFieldPolicy::Drop`
+    /// cannot be restored either.
+    pub fn to_upstream_json(&self, processed: &Value) -> Value {
+        let mut upstream = processed.clone();
+        let strip = |item: &mut Value| {
+            if let Some(obj) = item.as_object_mut() {
+                for field_name in self.computed_fields.keys() {
+                    if !self.fields.iter().any(|field| &field.name == field_name) {
+                        obj.remove(field_name);
+                    }
+                }
+            }
+        };
+
+        if is_paginated(&upstream) {
+            if let Some(items) = upstream.get_mut("results").and_then(|v| v.as_array_mut()) {
+                items.iter_mut().for_each(strip);
+            }
+        } else {
+            strip(&mut upstream);
+        }
+        upstream
+    }
 }
 
+/// Whether `data` is a paginated response, which carries its items in `results`
+fn is_paginated(data: &Value) -> bool {
+    data.as_object()
+        .is_some_and(|obj| obj.contains_key("results") && obj.contains_key("count"))
+}
+
+/// A problem found by `SchemaManager::validate_schemas`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The same schema name was loaded more than once; the last one won
+    DuplicateName { schema: String },
+    /// A field's type is neither a primitive nor a known schema or type
+    UnknownFieldType {
+        schema: String,
+        field: String,
+        type_name: String,
+    },
+    /// A nested field references a schema or type that doesn't exist
+    UnknownNestedType {
+        schema: String,
+        field: String,
+        type_name: String,
+    },
+    /// A query parameter's type is not a primitive type
+    InvalidQueryParamType {
+        schema: String,
+        param: String,
+        type_name: String,
+    },
+    /// A query parameter's default doesn't fit its type
+    InvalidQueryParamDefault {
+        schema: String,
+        param: String,
+        type_name: String,
+        default: String,
+    },
+    /// A computed field reads from a field the schema doesn't declare
+    UnknownComputedSource {
+        schema: String,
+        field: String,
+        from: String,
+    },
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaError::DuplicateName { schema } => {
+                write!(f, "schema '{}' is defined more than once", schema)
+            }
+            SchemaError::UnknownFieldType {
+                schema,
+                field,
+                type_name,
+            } => write!(
+                f,
+                "{}.{}: unknown field type '{}'",
+                schema, field, type_name
+            ),
+            SchemaError::UnknownNestedType {
+                schema,
+                field,
+                type_name,
+            } => write!(
+                f,
+                "{}.{}: nested type '{}' is not a known schema or type",
+                schema, field, type_name
+            ),
+            SchemaError::InvalidQueryParamType {
+                schema,
+                param,
+                type_name,
+            } => write!(
+                f,
+                "{}.query_params.{}: invalid type '{}'",
+                schema, param, type_name
+            ),
+            SchemaError::InvalidQueryParamDefault {
+                schema,
+                param,
+                type_name,
+                default,
+            } => write!(
+                f,
+                "{}.query_params.{}: default '{}' is not a valid '{}'",
+                schema, param, default, type_name
+            ),
+            SchemaError::UnknownComputedSource {
+                schema,
+                field,
+                from,
+            } => write!(
+                f,
+                "{}.computed_fields.{}: source field '{}' is not declared",
+                schema, field, from
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Scalar types accepted for fields and query parameters
+const PRIMITIVE_TYPES: &[&str] = &[
+    "String", "bool", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64",
+];
+
+/// Content types provided by the crate that nested fields may reference
+const BUILTIN_TYPES: &[&str] = &[
+    "Article", "Author", "Blog", "Event", "Launch", "Report", "Social",
+];
+
+/// Strip `Option<...>` and `Vec<...>` wrappers down to the element type
+fn element_type(type_name: &str) -> &str {
+    let mut inner = type_name.trim();
+    while let Some(stripped) = inner
+        .strip_prefix("Option<")
+        .or_else(|| inner.strip_prefix("Vec<"))
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        inner = stripped.trim();
+    }
+    inner
+}
+
+/// Whether a query parameter default can be sent as the primitive
+/// `type_name`
+///
+/// `Vec<...>` takes an array of fitting elements or a single one. `String`
+/// takes any scalar, since TOML strings that look like numbers are read as
+/// numbers; integer types take integers in their range.
+fn default_fits(type_name: &str, value: &QueryParamValue) -> bool {
+    let type_name = type_name.trim();
+    if let Some(inner) = type_name
+        .strip_prefix("Option<")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return default_fits(inner, value);
+    }
+    if let Some(inner) = type_name
+        .strip_prefix("Vec<")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return match value {
+            QueryParamValue::Array(items) => items.iter().all(|item| default_fits(inner, item)),
+            scalar => default_fits(inner, scalar),
+        };
+    }
+
+    match (type_name, value) {
+        (_, QueryParamValue::Array(_)) => false,
+        ("String", _) => true,
+        ("bool", QueryParamValue::Boolean(_)) => true,
+        ("f32" | "f64", QueryParamValue::Integer(_) | QueryParamValue::Float(_)) => true,
+        ("i8", QueryParamValue::Integer(i)) => i8::try_from(*i).is_ok(),
+        ("i16", QueryParamValue::Integer(i)) => i16::try_from(*i).is_ok(),
+        ("i32", QueryParamValue::Integer(i)) => i32::try_from(*i).is_ok(),
+        ("i64", QueryParamValue::Integer(_)) => true,
+        ("u8", QueryParamValue::Integer(i)) => u8::try_from(*i).is_ok(),
+        ("u16", QueryParamValue::Integer(i)) => u16::try_from(*i).is_ok(),
+        ("u32", QueryParamValue::Integer(i)) => u32::try_from(*i).is_ok(),
+        ("u64", QueryParamValue::Integer(i)) => u64::try_from(*i).is_ok(),
+        _ => false,
+    }
+}
+
+/// Summary of a schema for listing what can be queried
+///
+/// Fields keep their declared order; nested fields and query parameters
+/// are sorted by name. `Display` renders a `--help`-style block.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaDescription {
+    /// Name of the schema, which is also the endpoint name
+    pub name: String,
+    /// Fields with their types
+    pub fields: Vec<FieldDefinition>,
+    /// Fields holding other schemas, as (field, schema name)
+    pub nested_fields: Vec<(String, String)>,
+    /// Supported query parameters with their defaults and descriptions
+    pub query_params: Vec<QueryParamDefinition>,
+}
+
+impl std::fmt::Display for SchemaDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.name)?;
+        if !self.fields.is_empty() {
+            writeln!(f, "  Fields:")?;
+            for field in &self.fields {
+                let optional = if field.optional { " (optional)" } else { "" };
+                writeln!(f, "    {}: {}{}", field.name, field.type_name, optional)?;
+            }
+        }
+        if !self.nested_fields.is_empty() {
+            writeln!(f, "  Nested fields:")?;
+            for (field, schema) in &self.nested_fields {
+                writeln!(f, "    {}: {}", field, schema)?;
+            }
+        }
+        if !self.query_params.is_empty() {
+            writeln!(f, "  Query parameters:")?;
+            for param in &self.query_params {
+                write!(f, "    {}: {}", param.name, param.param_type)?;
+                if let Some(default) = &param.default {
+                    write!(f, " [default: {}]", default)?;
+                }
+                if let Some(description) = &param.description {
+                    write!(f, " - {}", description)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks a field value of a custom type, returning why it is invalid
+pub type TypeValidator = Arc<dyn Fn(&Value) -> Result<(), String> + Send + Sync>;
+
+/// Custom field types by name, consulted by `SchemaManager::apply_schema`
+#[derive(Clone, Default)]
+struct TypeRegistry {
+    validators: HashMap<String, TypeValidator>,
+}
+
+impl std::fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&String> = self.validators.keys().collect();
+        names.sort();
+        f.debug_set().entries(names).finish()
+    }
+}
+
+/// Built-in `datetime` type: an RFC 3339 timestamp string
+fn validate_datetime(value: &Value) -> Result<(), String> {
+    let text = value
+        .as_str()
+        .ok_or("expected an RFC 3339 timestamp string")?;
+    chrono::DateTime::parse_from_rfc3339(text)
+        .map(|_| ())
+        .map_err(|e| format!("'{}' is not an RFC 3339 timestamp: {}", text, e))
+}
+
+/// Built-in `url` type: an absolute `http` or `https` URL with a host
+fn validate_url(value: &Value) -> Result<(), String> {
+    let text = value.as_str().ok_or("expected a URL string")?;
+    let has_scheme = text.starts_with("http://") || text.starts_with("https://");
+    if has_scheme && urls::host(text).is_some() {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not an absolute http(s) URL", text))
+    }
+}
+
+/// Largest `limit` the Spaceflight News API accepts for a single page
+pub const DEFAULT_MAX_LIMIT: u32 = 100;
+
 /// Schema manager that loads and manages struct definitions
 #[derive(Debug, Clone)]
 pub struct SchemaManager {
     /// Loaded schemas by name
     schemas: HashMap<String, Schema>,
+    /// Upper bound applied to the `limit` query parameter
+    max_limit: u32,
+    /// Decimal places kept when formatting float query values
+    float_precision: usize,
+    /// How query values are escaped
+    query_encoding: QueryEncoding,
+    /// Schema names that were loaded more than once
+    duplicate_names: Vec<String>,
+    /// Custom field types and their validators
+    types: TypeRegistry,
+    /// Whether `apply_schema` emits fields in declared order
+    #[cfg(feature = "preserve_order")]
+    declared_order: bool,
 }
 
 impl SchemaManager {
     /// Create a new schema manager
     pub fn new() -> Self {
-        Self {
+        let mut manager = Self {
             schemas: HashMap::new(),
-        }
+            duplicate_names: Vec::new(),
+            max_limit: DEFAULT_MAX_LIMIT,
+            float_precision: DEFAULT_FLOAT_PRECISION,
+            query_encoding: QueryEncoding::default(),
+            types: TypeRegistry::default(),
+            #[cfg(feature = "preserve_order")]
+            declared_order: false,
+        };
+        manager.register_type("datetime", validate_datetime);
+        manager.register_type("url", validate_url);
+        manager
+    }
+
+    /// Register a custom field type checked by `apply_schema`
+    ///
+    /// Fields declared with type `name` (also inside `Vec<...>` or
+    /// `Option<...>`) have each value passed to `validator`, and an `Err`
+    /// fails `apply_schema` with the message. Missing and `null` values
+    /// are not checked. Registering an existing name replaces its
+    /// validator. The built-in `datetime` (RFC 3339 string) and `url`
+    /// (absolute http(s) URL) types are registered this way.
+    pub fn register_type<F>(&mut self, name: impl Into<String>, validator: F)
+    where
+        F: Fn(&Value) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.types
+            .validators
+            .insert(name.into(), Arc::new(validator));
+    }
+
+    /// Set the maximum page size the target API accepts
+    ///
+    /// `build_query_string` clamps any larger `limit` down to this value.
+    /// A page holds at least one item, so 0 is raised to 1.
+    pub fn set_max_limit(&mut self, max_limit: u32) {
+        self.max_limit = max_limit.max(1);
+    }
+
+    /// Make `apply_schema` emit each item's fields in schema order
+    ///
+    /// Fields come in the order the schema declares them, then nested and
+    /// computed fields by name, then any undeclared fields in the order
+    /// they arrived. Without this, objects keep their keys in arrival
+    /// order, as `serde_json` does with `preserve_order`. The feature also
+    /// keeps the field order of TOML schema files, which would otherwise
+    /// be read alphabetically.
+    #[cfg(feature = "preserve_order")]
+    pub fn set_declared_order(&mut self, declared_order: bool) {
+        self.declared_order = declared_order;
+    }
+
+    /// Set how many decimal places float query values keep
+    pub fn set_float_precision(&mut self, float_precision: usize) {
+        self.float_precision = float_precision;
+    }
+
+    /// Set how `build_query_string`, and a `RESTClient` holding this
+    /// manager, escape values
+    pub fn set_query_encoding(&mut self, query_encoding: QueryEncoding) {
+        self.query_encoding = query_encoding;
+    }
+
+    /// Get how query values are escaped
+    pub fn query_encoding(&self) -> QueryEncoding {
+        self.query_encoding
+    }
+
+    /// Get the maximum page size applied to `limit`
+    pub fn max_limit(&self) -> u32 {
+        self.max_limit
     }
 
     /// Load schemas from a TOML file
@@ -103,8 +632,8 @@ impl SchemaManager {
         // Parse schemas from the TOML structure
         if let Some(tables) = config.as_table() {
             for (name, value) in tables {
-                // Skip special sections like [types] and [config]
-                if name == "types" || name == "config" {
+                // Skip special sections like [types], [config] and [field_map]
+                if name == "types" || name == "config" || name == "field_map" {
                     continue;
                 }
 
@@ -123,38 +652,45 @@ impl SchemaManager {
                     continue;
                 }
 
+                // Skip computed_fields sections (they're processed as part of the main schema)
+                if name.contains(".computed_fields") {
+                    continue;
+                }
+
                 // Parse the schema
                 if let Some(_schema_table) = value.as_table() {
-                    // Get schema definition from the dedicated schema section
+                    // Get schema definition from the dedicated schema section, which
+                    // TOML nests under the endpoint table for `[name.schema]` headers
                     let schema_section_name = format!("{}.schema", name);
-                    let (fields, nested_fields) = if let Some(schema_section) =
-                        tables.get(&schema_section_name)
-                    {
+                    let schema_section = value
+                        .get("schema")
+                        .or_else(|| tables.get(&schema_section_name));
+                    let (fields, nested_fields) = if let Some(schema_section) = schema_section {
                         if let Some(schema_def_table) = schema_section.as_table() {
                             let mut fields = Vec::new();
                             let mut nested_fields = HashMap::new();
 
                             // Parse regular fields
                             for (field_name, field_type) in schema_def_table {
-                                if field_name != "nested_fields" {
-                                    if let Some(type_str) = field_type.as_str() {
-                                        fields.push(FieldDefinition {
-                                            name: field_name.clone(),
-                                            type_name: type_str.to_string(),
-                                            optional: false,
-                                        });
-                                    }
+                                if field_name != "nested_fields"
+                                    && let Some(type_str) = field_type.as_str()
+                                {
+                                    fields.push(FieldDefinition {
+                                        name: field_name.clone(),
+                                        type_name: type_str.to_string(),
+                                        optional: false,
+                                    });
                                 }
                             }
 
                             // Parse nested fields if they exist
-                            if let Some(nested_section) = schema_def_table.get("nested_fields") {
-                                if let Some(nested_table) = nested_section.as_table() {
-                                    for (field_name, field_type) in nested_table {
-                                        if let Some(type_str) = field_type.as_str() {
-                                            nested_fields
-                                                .insert(field_name.clone(), type_str.to_string());
-                                        }
+                            if let Some(nested_section) = schema_def_table.get("nested_fields")
+                                && let Some(nested_table) = nested_section.as_table()
+                            {
+                                for (field_name, field_type) in nested_table {
+                                    if let Some(type_str) = field_type.as_str() {
+                                        nested_fields
+                                            .insert(field_name.clone(), type_str.to_string());
                                     }
                                 }
                             }
@@ -170,20 +706,61 @@ impl SchemaManager {
                     // Parse query parameters if they exist
                     let mut query_params = HashMap::new();
                     let query_key = format!("{}.query_params", name);
-                    if let Some(query_section) = tables.get(&query_key) {
-                        if let Some(query_table) = query_section.as_table() {
-                            for (param_name, param_value) in query_table {
-                                // Simple parameter definition with value
-                                if let Some(value_str) = Self::toml_value_to_string(param_value) {
-                                    let param_definition = QueryParamDefinition {
-                                        name: param_name.clone(),
-                                        param_type: Self::infer_param_type(&value_str),
-                                        default: Some(Self::string_to_param_value(&value_str)),
-                                        description: None,
-                                    };
-                                    query_params.insert(param_name.clone(), param_definition);
-                                }
-                            }
+                    if let Some(query_section) =
+                        value.get("query_params").or_else(|| tables.get(&query_key))
+                        && let Some(query_table) = query_section.as_table()
+                    {
+                        for (param_name, param_value) in query_table {
+                            // Either a bare default value, or a table such as
+                            // `{ default = ["NASA"], array_format = "repeat" }`
+                            let (default_value, array_format, description) =
+                                match param_value.as_table() {
+                                    Some(table) => (
+                                        table.get("default"),
+                                        table
+                                            .get("array_format")
+                                            .and_then(|v| v.as_str())
+                                            .map(parse_array_format)
+                                            .transpose()?
+                                            .unwrap_or_default(),
+                                        table
+                                            .get("description")
+                                            .and_then(|v| v.as_str())
+                                            .map(str::to_string),
+                                    ),
+                                    None => (Some(param_value), ArrayFormat::default(), None),
+                                };
+                            let Some(default) = default_value.and_then(Self::toml_to_param_value)
+                            else {
+                                continue;
+                            };
+                            let param_definition = QueryParamDefinition {
+                                name: param_name.clone(),
+                                param_type: param_type_of(&default),
+                                default: Some(default),
+                                description,
+                                array_format,
+                            };
+                            query_params.insert(param_name.clone(), param_definition);
+                        }
+                    }
+
+                    // Parse computed fields, e.g. `domain = { from = "url", op = "host" }`
+                    let mut computed_fields = HashMap::new();
+                    let computed_key = format!("{}.computed_fields", name);
+                    if let Some(computed_section) = value
+                        .get("computed_fields")
+                        .or_else(|| tables.get(&computed_key))
+                        && let Some(computed_table) = computed_section.as_table()
+                    {
+                        for (field_name, spec) in computed_table {
+                            let computed: ComputedField = spec.clone().try_into().map_err(|e| {
+                                format!(
+                                    "Invalid computed field '{}' in '{}': {}",
+                                    field_name, name, e
+                                )
+                            })?;
+                            computed_fields.insert(field_name.clone(), computed);
                         }
                     }
 
@@ -192,16 +769,272 @@ impl SchemaManager {
                         fields,
                         nested_fields,
                         query_params,
+                        computed_fields,
+                        json_schema: value
+                            .get("json_schema")
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string),
+                        unknown_fields: value
+                            .get("unknown_fields")
+                            .map(|policy| policy.clone().try_into())
+                            .transpose()
+                            .map_err(|e| format!("Invalid unknown_fields in '{}': {}", name, e))?
+                            .unwrap_or_default(),
                     };
 
-                    self.schemas.insert(name.clone(), schema);
+                    self.insert_schema(schema);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load schemas from an OpenAPI 3 or Swagger 2 JSON document
+    ///
+    /// `source` is either an `http(s)://` URL or a path to a local file.
+    /// See `load_from_openapi_value` for how the spec is mapped.
+    pub async fn load_from_openapi(
+        &mut self,
+        source: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let spec: Value = if source.starts_with("http://") || source.starts_with("https://") {
+            reqwest::get(source)
+                .await?
+                .error_for_status()?
+                .json()
+                .await?
+        } else {
+            serde_json::from_str(&tokio::fs::read_to_string(source).await?)?
+        };
+        self.load_from_openapi_value(&spec)
+    }
+
+    /// Load schemas from a parsed OpenAPI 3 or Swagger 2 document
+    ///
+    /// Every object model under `components/schemas` (or `definitions`)
+    /// becomes a `Schema` of the same name. Properties that point at another
+    /// model, directly or as an array, are recorded in `nested_fields`;
+    /// everything else becomes a field. Query parameters of each `GET`
+    /// operation are attached to the model it returns, looking through
+    /// paginated wrappers to the model of their `results`. `$ref`s are
+    /// resolved within the document.
+    pub fn load_from_openapi_value(
+        &mut self,
+        spec: &Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let models = spec
+            .pointer("/components/schemas")
+            .or_else(|| spec.get("definitions"))
+            .and_then(|v| v.as_object())
+            .ok_or("OpenAPI document has no component schemas")?;
+
+        let mut schemas = HashMap::new();
+        for (model_name, model) in models {
+            let model = resolve_ref(spec, model);
+            let Some(properties) = model.get("properties").and_then(|v| v.as_object()) else {
+                continue;
+            };
+            let required: Vec<&str> = model
+                .get("required")
+                .and_then(|v| v.as_array())
+                .map(|names| names.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+
+            let mut fields = Vec::new();
+            let mut nested_fields = HashMap::new();
+            for (field_name, property) in properties {
+                let type_name = openapi_type_name(spec, property);
+                if refers_to_model(spec, property) {
+                    nested_fields.insert(field_name.clone(), type_name);
+                } else {
+                    let nullable = property
+                        .get("nullable")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    fields.push(FieldDefinition {
+                        name: field_name.clone(),
+                        type_name,
+                        optional: nullable || !required.contains(&field_name.as_str()),
+                    });
+                }
+            }
+
+            schemas.insert(
+                model_name.clone(),
+                Schema {
+                    name: model_name.clone(),
+                    fields,
+                    nested_fields,
+                    query_params: HashMap::new(),
+                    computed_fields: HashMap::new(),
+                    json_schema: None,
+                    unknown_fields: UnknownFieldPolicy::default(),
+                },
+            );
+        }
+
+        if let Some(paths) = spec.get("paths").and_then(|v| v.as_object()) {
+            for path_item in paths.values() {
+                let Some(operation) = path_item.get("get") else {
+                    continue;
+                };
+                let Some(model_name) = openapi_response_model(spec, operation) else {
+                    continue;
+                };
+                let Some(schema) = schemas.get_mut(&model_name) else {
+                    continue;
+                };
+
+                let parameters = path_item
+                    .get("parameters")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .chain(operation.get("parameters").and_then(|v| v.as_array()))
+                    .flatten();
+                for parameter in parameters {
+                    let parameter = resolve_ref(spec, parameter);
+                    if parameter.get("in").and_then(|v| v.as_str()) != Some("query") {
+                        continue;
+                    }
+                    let Some(param_name) = parameter.get("name").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    // OpenAPI 3 nests the type under `schema`, Swagger 2 does not
+                    let param_schema = parameter
+                        .get("schema")
+                        .map_or(parameter, |s| resolve_ref(spec, s));
+                    schema.query_params.insert(
+                        param_name.to_string(),
+                        QueryParamDefinition {
+                            name: param_name.to_string(),
+                            param_type: openapi_type_name(spec, param_schema),
+                            default: param_schema.get("default").and_then(json_to_param_value),
+                            description: parameter
+                                .get("description")
+                                .and_then(|v| v.as_str())
+                                .map(str::to_string),
+                            // `explode: true` asks for one `key=value` pair per item
+                            array_format: match parameter.get("explode").and_then(|v| v.as_bool()) {
+                                Some(true) => ArrayFormat::Repeat,
+                                _ => ArrayFormat::Comma,
+                            },
+                        },
+                    );
                 }
             }
         }
 
+        for schema in schemas.into_values() {
+            self.insert_schema(schema);
+        }
         Ok(())
     }
 
+    /// Add a schema, remembering if it replaces one of the same name
+    fn insert_schema(&mut self, schema: Schema) {
+        if let Some(previous) = self.schemas.insert(schema.name.clone(), schema) {
+            self.duplicate_names.push(previous.name);
+        }
+    }
+
+    /// Check the loaded schemas for consistency without making requests
+    ///
+    /// Reports every problem at once: schema names loaded more than once,
+    /// field and nested field types that are neither primitives, registered
+    /// types, loaded schemas nor the crate's content types, query
+    /// parameters with a non-primitive type or a default that doesn't fit
+    /// it, and computed fields reading undeclared fields.
+    pub fn validate_schemas(&self) -> Result<(), Vec<SchemaError>> {
+        let mut errors: Vec<SchemaError> = self
+            .duplicate_names
+            .iter()
+            .map(|schema| SchemaError::DuplicateName {
+                schema: schema.clone(),
+            })
+            .collect();
+
+        let is_known_type = |type_name: &str| {
+            let element = element_type(type_name);
+            PRIMITIVE_TYPES.contains(&element)
+                || BUILTIN_TYPES.contains(&element)
+                || self.types.validators.contains_key(element)
+                || self.schemas.contains_key(element)
+        };
+
+        let mut names: Vec<&String> = self.schemas.keys().collect();
+        names.sort();
+        for name in names {
+            let schema = &self.schemas[name];
+
+            for field in &schema.fields {
+                if !is_known_type(&field.type_name) {
+                    errors.push(SchemaError::UnknownFieldType {
+                        schema: name.clone(),
+                        field: field.name.clone(),
+                        type_name: field.type_name.clone(),
+                    });
+                }
+            }
+
+            let mut nested: Vec<_> = schema.nested_fields.iter().collect();
+            nested.sort();
+            for (field, type_name) in nested {
+                if !is_known_type(type_name) {
+                    errors.push(SchemaError::UnknownNestedType {
+                        schema: name.clone(),
+                        field: field.clone(),
+                        type_name: type_name.clone(),
+                    });
+                }
+            }
+
+            let mut params: Vec<_> = schema.query_params.iter().collect();
+            params.sort_by(|a, b| a.0.cmp(b.0));
+            for (param, definition) in params {
+                if !PRIMITIVE_TYPES.contains(&element_type(&definition.param_type)) {
+                    errors.push(SchemaError::InvalidQueryParamType {
+                        schema: name.clone(),
+                        param: param.clone(),
+                        type_name: definition.param_type.clone(),
+                    });
+                } else if let Some(default) = &definition.default
+                    && !default_fits(&definition.param_type, default)
+                {
+                    errors.push(SchemaError::InvalidQueryParamDefault {
+                        schema: name.clone(),
+                        param: param.clone(),
+                        type_name: definition.param_type.clone(),
+                        default: default.format(self.float_precision),
+                    });
+                }
+            }
+
+            // Without declared fields there is nothing to check sources against
+            if !schema.fields.is_empty() {
+                let mut computed: Vec<_> = schema.computed_fields.iter().collect();
+                computed.sort_by(|a, b| a.0.cmp(b.0));
+                for (field, definition) in computed {
+                    let declared = schema.fields.iter().any(|f| f.name == definition.from)
+                        || schema.nested_fields.contains_key(&definition.from);
+                    if !declared {
+                        errors.push(SchemaError::UnknownComputedSource {
+                            schema: name.clone(),
+                            field: field.clone(),
+                            from: definition.from.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Convert TOML value to string
     fn toml_value_to_string(value: &toml::Value) -> Option<String> {
         match value {
@@ -213,16 +1046,15 @@ impl SchemaManager {
         }
     }
 
-    /// Infer parameter type from string value
-    fn infer_param_type(value: &str) -> String {
-        if value.parse::<i64>().is_ok() {
-            "i64".to_string()
-        } else if value.parse::<f64>().is_ok() {
-            "f64".to_string()
-        } else if value.parse::<bool>().is_ok() {
-            "bool".to_string()
-        } else {
-            "String".to_string()
+    /// Convert a TOML default into a query parameter value, reading
+    /// scalars the same way as `string_to_param_value`
+    fn toml_to_param_value(value: &toml::Value) -> Option<QueryParamValue> {
+        match value {
+            toml::Value::Array(items) => Some(QueryParamValue::Array(
+                items.iter().filter_map(Self::toml_to_param_value).collect(),
+            )),
+            other => Self::toml_value_to_string(other)
+                .map(|value_str| Self::string_to_param_value(&value_str)),
         }
     }
 
@@ -253,17 +1085,22 @@ impl SchemaManager {
                 .unwrap_or("String")
                 .to_string();
 
-            let default = if let Some(default_value) = table.get("default") {
-                Some(match default_value {
+            let default = table
+                .get("default")
+                .map(|default_value| match default_value {
                     toml::Value::String(s) => QueryParamValue::String(s.clone()),
                     toml::Value::Integer(i) => QueryParamValue::Integer(*i),
                     toml::Value::Float(f) => QueryParamValue::Float(*f),
                     toml::Value::Boolean(b) => QueryParamValue::Boolean(*b),
                     _ => QueryParamValue::String(default_value.to_string()),
-                })
-            } else {
-                None
-            };
+                });
+
+            let array_format = table
+                .get("array_format")
+                .and_then(|v| v.as_str())
+                .map(parse_array_format)
+                .transpose()?
+                .unwrap_or_default();
 
             let description = table
                 .get("description")
@@ -275,6 +1112,7 @@ impl SchemaManager {
                 param_type,
                 default,
                 description,
+                array_format,
             })
         } else if let Some(type_str) = value.as_str() {
             // Simple string format like "String" or "u32"
@@ -283,6 +1121,7 @@ impl SchemaManager {
                 param_type: type_str.to_string(),
                 default: None,
                 description: None,
+                array_format: ArrayFormat::default(),
             })
         } else {
             Err(format!("Invalid query parameter definition for {}", name).into())
@@ -299,89 +1138,644 @@ impl SchemaManager {
         self.schemas.keys().collect()
     }
 
+    /// Describe every loaded schema, sorted by name
+    ///
+    /// Covers schemas from every file or spec loaded into this manager.
+    pub fn describe(&self) -> Vec<SchemaDescription> {
+        let mut descriptions: Vec<SchemaDescription> = self
+            .schemas
+            .values()
+            .map(|schema| {
+                let mut query_params: Vec<QueryParamDefinition> =
+                    schema.query_params.values().cloned().collect();
+                query_params.sort_by(|a, b| a.name.cmp(&b.name));
+                let mut nested_fields: Vec<(String, String)> = schema
+                    .nested_fields
+                    .iter()
+                    .map(|(field, schema)| (field.clone(), schema.clone()))
+                    .collect();
+                nested_fields.sort();
+                SchemaDescription {
+                    name: schema.name.clone(),
+                    fields: schema.fields.clone(),
+                    nested_fields,
+                    query_params,
+                }
+            })
+            .collect();
+        descriptions.sort_by(|a, b| a.name.cmp(&b.name));
+        descriptions
+    }
+
     /// Apply a schema to JSON data, returning a processed Value
+    ///
+    /// Fields of registered custom types (see `register_type`) are
+    /// validated first, failing on the first invalid value. Undeclared
+    /// fields are then handled per the schema's `unknown_fields` policy
+    /// before computed fields are added. With the `preserve_order` feature,
+    /// `set_declared_order` also puts the fields in schema order.
     pub fn apply_schema(
         &self,
         schema_name: &str,
         data: &Value,
     ) -> Result<Value, Box<dyn std::error::Error>> {
-        let _schema = self
+        let schema = self
             .get_schema(schema_name)
             .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
 
-        // Handle paginated responses
-        let data_to_process = if let Some(obj) = data.as_object() {
-            // If this looks like a paginated response, process the "results" array
-            if obj.contains_key("results") && obj.contains_key("count") {
-                data
-            } else {
-                // For single objects, wrap them in a structure that matches our processing
-                data
+        let mut result = data.clone();
+        let has_custom_types = schema.fields.iter().any(|field| {
+            self.types
+                .validators
+                .contains_key(element_type(&field.type_name))
+        });
+        if schema.computed_fields.is_empty()
+            && schema.unknown_fields == UnknownFieldPolicy::Keep
+            && !has_custom_types
+            && !self.orders_fields()
+        {
+            return Ok(result);
+        }
+
+        // Paginated responses carry their items in "results"; anything else
+        // is treated as a single item
+        if is_paginated(&result) {
+            if let Some(items) = result.get_mut("results").and_then(|v| v.as_array_mut()) {
+                for item in items {
+                    self.apply_to_item(schema, item)?;
+                }
             }
         } else {
-            data
+            self.apply_to_item(schema, &mut result)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Apply a schema to a single JSON object
+    fn apply_to_item(
+        &self,
+        schema: &Schema,
+        item: &mut Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.check_custom_types(schema, item)?;
+        Self::apply_unknown_field_policy(schema, item)?;
+        Self::add_computed_fields(schema, item);
+        #[cfg(feature = "preserve_order")]
+        if self.declared_order {
+            Self::order_fields(schema, item);
+        }
+        Ok(())
+    }
+
+    /// Whether `apply_schema` reorders fields
+    fn orders_fields(&self) -> bool {
+        #[cfg(feature = "preserve_order")]
+        return self.declared_order;
+        #[cfg(not(feature = "preserve_order"))]
+        false
+    }
+
+    /// Rebuild a single JSON object with its fields in schema order
+    #[cfg(feature = "preserve_order")]
+    fn order_fields(schema: &Schema, item: &mut Value) {
+        let Some(obj) = item.as_object_mut() else {
+            return;
         };
+        let mut nested: Vec<&String> = schema.nested_fields.keys().collect();
+        nested.sort();
+        let mut computed: Vec<&String> = schema.computed_fields.keys().collect();
+        computed.sort();
 
-        if let Some(obj) = data_to_process.as_object() {
-            let mut result = serde_json::Map::new();
+        let mut rest = std::mem::take(obj);
+        let declared = schema
+            .fields
+            .iter()
+            .map(|field| &field.name)
+            .chain(nested)
+            .chain(computed);
+        for key in declared {
+            if let Some(value) = rest.shift_remove(key) {
+                obj.insert(key.clone(), value);
+            }
+        }
+        obj.extend(rest);
+    }
 
-            // Copy all fields by default
-            for (key, value) in obj {
-                result.insert(key.clone(), value.clone());
+    /// Run the registered validators over the custom-typed fields of a
+    /// single JSON object
+    fn check_custom_types(
+        &self,
+        schema: &Schema,
+        item: &Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(obj) = item.as_object() else {
+            return Ok(());
+        };
+        for field in &schema.fields {
+            let Some(validator) = self.types.validators.get(element_type(&field.type_name)) else {
+                continue;
+            };
+            let values = match obj.get(&field.name) {
+                None | Some(Value::Null) => continue,
+                Some(Value::Array(items)) if field.type_name.contains("Vec<") => {
+                    items.iter().collect()
+                }
+                Some(value) => vec![value],
+            };
+            for value in values.into_iter().filter(|value| !value.is_null()) {
+                validator(value).map_err(|e| {
+                    format!(
+                        "Schema '{}' field '{}' ({}): {}",
+                        schema.name,
+                        field.name,
+                        element_type(&field.type_name),
+                        e
+                    )
+                })?;
             }
+        }
+        Ok(())
+    }
 
-            // Apply field-specific processing if needed
-            // For now, we're just passing through the data as-is
-            // In a more sophisticated implementation, you could:
-            // 1. Validate field types
-            // 2. Apply transformations
-            // 3. Handle nested schema application
+    /// Keep only the fields a schema declares
+    ///
+    /// Unlike `apply_schema`, this ignores the schema's `unknown_fields`
+    /// policy and computed fields: each item keeps exactly its `fields`
+    /// and `nested_fields` that are present. A nested field whose type
+    /// (ignoring `Vec<...>` and `Option<...>`) names another loaded schema
+    /// is projected with that schema, recursively; other nested values
+    /// are kept whole.
+    pub fn project(
+        &self,
+        schema_name: &str,
+        data: &Value,
+    ) -> Result<Value, Box<dyn std::error::Error>> {
+        let schema = self
+            .get_schema(schema_name)
+            .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
 
-            Ok(Value::Object(result))
+        let mut result = data.clone();
+        if is_paginated(&result) {
+            if let Some(items) = result.get_mut("results").and_then(|v| v.as_array_mut()) {
+                for item in items {
+                    self.project_value(schema, item);
+                }
+            }
         } else {
-            // For arrays or other types, pass through as-is
-            Ok(data_to_process.clone())
+            self.project_value(schema, &mut result);
         }
+        Ok(result)
+    }
+
+    /// Project an object, or each object of an array, onto `schema`
+    fn project_value(&self, schema: &Schema, value: &mut Value) {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    self.project_value(schema, item);
+                }
+            }
+            Value::Object(obj) => {
+                obj.retain(|key, _| {
+                    schema.fields.iter().any(|field| &field.name == key)
+                        || schema.nested_fields.contains_key(key)
+                });
+                for (field_name, type_name) in &schema.nested_fields {
+                    if let Some(nested_schema) = self.get_schema(element_type(type_name))
+                        && let Some(nested) = obj.get_mut(field_name)
+                    {
+                        self.project_value(nested_schema, nested);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keep, drop or reject the undeclared fields of a single JSON object
+    fn apply_unknown_field_policy(
+        schema: &Schema,
+        item: &mut Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(obj) = item.as_object_mut() else {
+            return Ok(());
+        };
+        let is_declared = |key: &str| {
+            schema.fields.iter().any(|field| field.name == key)
+                || schema.nested_fields.contains_key(key)
+                || schema.computed_fields.contains_key(key)
+        };
+
+        match schema.unknown_fields {
+            UnknownFieldPolicy::Keep => {}
+            UnknownFieldPolicy::Drop => obj.retain(|key, _| is_declared(key)),
+            UnknownFieldPolicy::Error => {
+                let mut unknown: Vec<&str> = obj
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|key| !is_declared(key))
+                    .collect();
+                if !unknown.is_empty() {
+                    unknown.sort();
+                    return Err(format!(
+                        "Schema '{}' does not declare field(s): {}",
+                        schema.name,
+                        unknown.join(", ")
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Populate a schema's computed fields on a single JSON object
+    fn add_computed_fields(schema: &Schema, item: &mut Value) {
+        if let Some(obj) = item.as_object_mut() {
+            for (field_name, computed) in &schema.computed_fields {
+                let value = computed.compute(obj);
+                obj.insert(field_name.clone(), value);
+            }
+        }
+    }
+
+    /// Validate JSON data against the schema's `json_schema` document
+    ///
+    /// Returns every violation found, each prefixed with the JSON pointer of
+    /// the offending value (`/` for the root); an empty list means the data is valid. Unlike
+    /// `apply_schema`, this enforces the full JSON Schema vocabulary such as
+    /// `format` and `enum`. Fails if the schema has no `json_schema` or the
+    /// document cannot be read or compiled. The document is read on every
+    /// call, so edits take effect without reloading.
+    #[cfg(feature = "jsonschema")]
+    pub fn validate(
+        &self,
+        schema_name: &str,
+        data: &Value,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let schema = self
+            .get_schema(schema_name)
+            .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
+        let path = schema
+            .json_schema
+            .as_deref()
+            .ok_or_else(|| format!("Schema '{}' has no json_schema", schema_name))?;
+
+        let document: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let validator = jsonschema::validator_for(&document)
+            .map_err(|e| format!("Invalid JSON Schema '{}': {}", path, e))?;
+
+        Ok(validator
+            .iter_errors(data)
+            .map(|error| {
+                let pointer = error.instance_path().to_string();
+                let pointer = if pointer.is_empty() { "/" } else { &pointer };
+                format!("{}: {}", pointer, error)
+            })
+            .collect())
+    }
+
+    /// Validate pagination parameters before they are sent
+    ///
+    /// `limit` (or `page_size`) must be a positive integer and is clamped
+    /// to `max_limit` rather than rejected, since the API fails opaquely on
+    /// oversized pages. `offset` must be a non-negative integer and `page`
+    /// a positive one.
+    fn normalize_pagination_param(
+        &self,
+        name: &str,
+        value: String,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        match name {
+            "limit" | "page_size" => {
+                let limit: u32 =
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|limit| *limit > 0)
+                        .ok_or_else(|| {
+                            format!("Invalid {} '{}': expected a positive integer", name, value)
+                        })?;
+                Ok(limit.min(self.max_limit).to_string())
+            }
+            "page" => {
+                value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|page| *page > 0)
+                    .ok_or_else(|| {
+                        format!("Invalid page '{}': expected a positive integer", value)
+                    })?;
+                Ok(value)
+            }
+            "offset" => {
+                value.parse::<u64>().map_err(|_| {
+                    format!(
+                        "Invalid offset '{}': expected a non-negative integer",
+                        value
+                    )
+                })?;
+                Ok(value)
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Default query parameters declared by a schema, formatted for a URL
+    ///
+    /// These are the values `build_query_string` fills in for parameters
+    /// the caller doesn't pass, so runtime parameters can be merged over
+    /// them with `extend`. Values go through the same `limit`/`offset`
+    /// normalization, with `limit` clamped to `max_limit`, but are not
+    /// URL-encoded. Array defaults are joined with commas whatever their
+    /// `array_format`, since the map holds one value per key. Parameters
+    /// without a default are left out.
+    pub fn default_params(
+        &self,
+        schema_name: &str,
+    ) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        let schema = self
+            .get_schema(schema_name)
+            .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
+
+        let mut defaults = HashMap::new();
+        for (param_name, param_def) in &schema.query_params {
+            if let Some(default_value) = &param_def.default {
+                let value = self.normalize_pagination_param(
+                    param_name,
+                    default_value.format(self.float_precision),
+                )?;
+                defaults.insert(param_name.clone(), value);
+            }
+        }
+        Ok(defaults)
     }
 
     /// Build query parameters string from provided parameters
+    ///
+    /// Parameters are emitted sorted by name. `limit` values above the
+    /// configured maximum are clamped; invalid `limit` or `offset` values
+    /// produce an error. Values are escaped per `set_query_encoding`.
     pub fn build_query_string(
         &self,
         schema_name: &str,
         params: &HashMap<String, String>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let values: HashMap<String, QueryParamValue> = params
+            .iter()
+            .map(|(key, value)| (key.clone(), QueryParamValue::String(value.clone())))
+            .collect();
+        self.build_query_string_from_values(schema_name, &values)
+    }
+
+    /// Build a query string from typed values, which may include arrays
+    ///
+    /// Array values are written according to each parameter's
+    /// `array_format`, with every element URL-encoded on its own. Otherwise
+    /// behaves like `build_query_string`.
+    pub fn build_query_string_from_values(
+        &self,
+        schema_name: &str,
+        params: &HashMap<String, QueryParamValue>,
     ) -> Result<String, Box<dyn std::error::Error>> {
         let schema = self
             .get_schema(schema_name)
             .ok_or_else(|| format!("Schema '{}' not found", schema_name))?;
 
-        let mut query_pairs = Vec::new();
+        // Collected as (key, encoded value) and sorted by key so the same
+        // inputs always produce the same query string
+        let mut query_pairs: Vec<(&str, String)> = Vec::new();
 
         // Add provided parameters
         for (key, value) in params {
-            if schema.query_params.contains_key(key) {
-                query_pairs.push(format!("{}={}", key, urlencoding::encode(value)));
+            if let Some(param_def) = schema.query_params.get(key) {
+                for encoded in self.encode_param(key, value, param_def.array_format)? {
+                    query_pairs.push((key, encoded));
+                }
             }
         }
 
         // Add default parameters for any that weren't provided
         for (param_name, param_def) in &schema.query_params {
-            if !params.contains_key(param_name) {
-                if let Some(default_value) = &param_def.default {
-                    query_pairs.push(format!(
-                        "{}={}",
-                        param_name,
-                        urlencoding::encode(&default_value.to_string())
-                    ));
+            if !params.contains_key(param_name)
+                && let Some(default_value) = &param_def.default
+            {
+                for encoded in
+                    self.encode_param(param_name, default_value, param_def.array_format)?
+                {
+                    query_pairs.push((param_name, encoded));
                 }
             }
         }
 
+        // Stable, so repeated keys keep the order of their values
+        query_pairs.sort_by(|a, b| a.0.cmp(b.0));
+        let query_pairs: Vec<String> = query_pairs
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+
         if query_pairs.is_empty() {
             Ok(String::new())
         } else {
             Ok(format!("?{}", query_pairs.join("&")))
         }
     }
+
+    /// URL-encode one parameter value, giving one entry per `key=value`
+    /// pair to emit; an empty array emits nothing
+    fn encode_param(
+        &self,
+        key: &str,
+        value: &QueryParamValue,
+        array_format: ArrayFormat,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        match value {
+            QueryParamValue::Array(items) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| self.escape(&item.format(self.float_precision)))
+                    .collect();
+                Ok(match array_format {
+                    _ if items.is_empty() => Vec::new(),
+                    ArrayFormat::Comma => vec![items.join(",")],
+                    ArrayFormat::Repeat => items,
+                })
+            }
+            scalar => {
+                let value =
+                    self.normalize_pagination_param(key, scalar.format(self.float_precision))?;
+                Ok(vec![self.escape(&value)])
+            }
+        }
+    }
+
+    /// Escape a query value per `query_encoding`
+    fn escape(&self, value: &str) -> String {
+        self.query_encoding.escape(value)
+    }
+}
+
+/// Follow `$ref` links within an OpenAPI document until a definition is reached
+fn resolve_ref<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut current = value;
+    // Bounded so a reference cycle cannot loop forever
+    for _ in 0..32 {
+        let target = current
+            .get("$ref")
+            .and_then(|v| v.as_str())
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| spec.pointer(pointer));
+        match target {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// The `$ref` of a property, looking through a single-entry `allOf`
+fn property_ref(value: &Value) -> Option<&Value> {
+    value.get("$ref").map(|_| value).or_else(|| {
+        value
+            .get("allOf")
+            .and_then(|v| v.as_array())
+            .filter(|all_of| all_of.len() == 1)
+            .map(|all_of| &all_of[0])
+            .filter(|inner| inner.get("$ref").is_some())
+    })
+}
+
+/// Name of the model a `$ref` points at, looking through a single `allOf`
+fn ref_model_name(value: &Value) -> Option<&str> {
+    property_ref(value)?
+        .get("$ref")?
+        .as_str()?
+        .rsplit('/')
+        .next()
+}
+
+/// Whether a property holds another object model or an array of them
+fn refers_to_model(spec: &Value, property: &Value) -> bool {
+    let target = match property.get("items") {
+        Some(items) if property.get("type").and_then(|v| v.as_str()) == Some("array") => items,
+        _ => property,
+    };
+    property_ref(target)
+        .is_some_and(|reference| resolve_ref(spec, reference).get("properties").is_some())
+}
+
+/// Map an OpenAPI type to the Rust-style type names used in schema files
+fn openapi_type_name(spec: &Value, property: &Value) -> String {
+    openapi_type_name_within(spec, property, &mut Vec::new())
+}
+
+/// [`openapi_type_name`], given the `$ref`s already being followed
+///
+/// A reference that leads back to one of them is a cycle and maps to
+/// `String`.
+fn openapi_type_name_within<'a>(
+    spec: &'a Value,
+    property: &'a Value,
+    following: &mut Vec<&'a str>,
+) -> String {
+    if let Some(reference) = property_ref(property) {
+        let name = reference
+            .get("$ref")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if following.contains(&name) {
+            return "String".to_string();
+        }
+        // Enums and aliases are references too, but carry no properties
+        let target = resolve_ref(spec, reference);
+        return match ref_model_name(reference) {
+            Some(model_name) if target.get("properties").is_some() => model_name.to_string(),
+            _ if target.get("$ref").is_none() => {
+                following.push(name);
+                let type_name = openapi_type_name_within(spec, target, following);
+                following.pop();
+                type_name
+            }
+            _ => "String".to_string(),
+        };
+    }
+
+    let format = property.get("format").and_then(|v| v.as_str());
+    match property.get("type").and_then(|v| v.as_str()) {
+        Some("integer") if format == Some("int32") => "i32".to_string(),
+        Some("integer") => "i64".to_string(),
+        Some("number") => "f64".to_string(),
+        Some("boolean") => "bool".to_string(),
+        Some("array") => {
+            let items = property.get("items").unwrap_or(&Value::Null);
+            format!("Vec<{}>", openapi_type_name_within(spec, items, following))
+        }
+        _ => "String".to_string(),
+    }
+}
+
+/// Model returned by a `GET` operation, unwrapping paginated list responses
+fn openapi_response_model(spec: &Value, operation: &Value) -> Option<String> {
+    let response = resolve_ref(spec, operation.pointer("/responses/200")?);
+    let body = response
+        .pointer("/content/application~1json/schema")
+        .or_else(|| response.get("schema"))?;
+
+    let resolved = resolve_ref(spec, body);
+    if let Some(items) = resolved.pointer("/properties/results/items") {
+        return ref_model_name(items).map(str::to_string);
+    }
+    if let Some(items) = resolved.get("items") {
+        return ref_model_name(items).map(str::to_string);
+    }
+    ref_model_name(body).map(str::to_string)
+}
+
+/// Parse the `array_format` of a query parameter declared in TOML
+fn parse_array_format(value: &str) -> Result<ArrayFormat, Box<dyn std::error::Error>> {
+    match value {
+        "comma" => Ok(ArrayFormat::Comma),
+        "repeat" => Ok(ArrayFormat::Repeat),
+        other => Err(format!(
+            "Invalid array_format '{}', expected \"comma\" or \"repeat\"",
+            other
+        )
+        .into()),
+    }
+}
+
+/// Schema type name for a query parameter value
+fn param_type_of(value: &QueryParamValue) -> String {
+    match value {
+        QueryParamValue::String(_) => "String".to_string(),
+        QueryParamValue::Integer(_) => "i64".to_string(),
+        QueryParamValue::Float(_) => "f64".to_string(),
+        QueryParamValue::Boolean(_) => "bool".to_string(),
+        QueryParamValue::Array(items) => format!(
+            "Vec<{}>",
+            items
+                .first()
+                .map_or_else(|| "String".to_string(), param_type_of)
+        ),
+    }
+}
+
+/// Convert a JSON default value into a query parameter value
+fn json_to_param_value(value: &Value) -> Option<QueryParamValue> {
+    match value {
+        Value::String(s) => Some(QueryParamValue::String(s.clone())),
+        Value::Bool(b) => Some(QueryParamValue::Boolean(*b)),
+        Value::Number(n) => n
+            .as_i64()
+            .map(QueryParamValue::Integer)
+            .or_else(|| n.as_f64().map(QueryParamValue::Float)),
+        Value::Array(items) => Some(QueryParamValue::Array(
+            items.iter().filter_map(json_to_param_value).collect(),
+        )),
+        _ => None,
+    }
 }
 
 impl Default for SchemaManager {
@@ -389,3 +1783,844 @@ impl Default for SchemaManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A manager loaded from `toml`
+    fn manager(toml: &str) -> SchemaManager {
+        let mut manager = SchemaManager::new();
+        manager
+            .load_from_toml_value(&toml::from_str(toml).unwrap())
+            .unwrap();
+        manager
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    const SEARCH: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+
+        [articles.query_params]
+        search = "x"
+    "#;
+
+    #[test]
+    fn percent_encoding_escapes_reserved_characters() {
+        let manager = manager(SEARCH);
+        let query = params(&[("search", "a b+c&d=e")]);
+        assert_eq!(
+            manager.build_query_string("articles", &query).unwrap(),
+            "?search=a%20b%2Bc%26d%3De"
+        );
+    }
+
+    #[test]
+    fn form_encoding_writes_spaces_as_plus() {
+        let mut manager = manager(SEARCH);
+        manager.set_query_encoding(QueryEncoding::Form);
+        let query = params(&[("search", "a b+c&d=e")]);
+        assert_eq!(
+            manager.build_query_string("articles", &query).unwrap(),
+            "?search=a+b%2Bc%26d%3De"
+        );
+    }
+
+    const ARRAYS: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+
+        [articles.query_params]
+        news_site__in = ["NASA", "SpaceNews"]
+        news_site = { default = ["NASA"], array_format = "repeat" }
+    "#;
+
+    fn strings(items: &[&str]) -> QueryParamValue {
+        QueryParamValue::Array(
+            items
+                .iter()
+                .map(|item| QueryParamValue::String(item.to_string()))
+                .collect(),
+        )
+    }
+
+    fn array_query(key: &str, value: QueryParamValue) -> String {
+        let params = HashMap::from([(key.to_string(), value)]);
+        manager(ARRAYS)
+            .build_query_string_from_values("articles", &params)
+            .unwrap()
+    }
+
+    #[test]
+    fn comma_arrays_join_escaped_elements() {
+        assert_eq!(
+            array_query("news_site__in", strings(&["Space News", "A&B", "x,y"])),
+            "?news_site=NASA&news_site__in=Space%20News,A%26B,x%2Cy"
+        );
+    }
+
+    #[test]
+    fn repeat_arrays_repeat_the_key() {
+        assert_eq!(
+            array_query("news_site", strings(&["Space News", "a=b"])),
+            "?news_site=Space%20News&news_site=a%3Db&news_site__in=NASA,SpaceNews"
+        );
+    }
+
+    #[test]
+    fn empty_arrays_emit_nothing() {
+        assert_eq!(
+            array_query("news_site__in", strings(&[])),
+            "?news_site=NASA"
+        );
+        assert_eq!(
+            array_query("news_site", strings(&[])),
+            "?news_site__in=NASA,SpaceNews"
+        );
+    }
+
+    #[test]
+    fn array_defaults_use_their_format() {
+        let manager = manager(ARRAYS);
+        assert_eq!(
+            manager
+                .build_query_string("articles", &HashMap::new())
+                .unwrap(),
+            "?news_site=NASA&news_site__in=NASA,SpaceNews"
+        );
+    }
+
+    /// An `articles` schema with `id` and `title` and the given policy
+    fn with_policy(policy: &str) -> SchemaManager {
+        manager(&format!(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+            unknown_fields = "{}"
+
+            [articles.schema]
+            id = "u32"
+            title = "String"
+            "#,
+            policy
+        ))
+    }
+
+    fn item() -> Value {
+        serde_json::json!({ "id": 1, "title": "Launch", "extra": true })
+    }
+
+    fn page() -> Value {
+        serde_json::json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [item(), { "id": 2, "title": "Landing" }],
+        })
+    }
+
+    #[test]
+    fn keep_passes_unknown_fields_through() {
+        let manager = with_policy("keep");
+        assert_eq!(manager.apply_schema("articles", &item()).unwrap(), item());
+        assert_eq!(manager.apply_schema("articles", &page()).unwrap(), page());
+    }
+
+    #[test]
+    fn drop_removes_unknown_fields() {
+        let manager = with_policy("drop");
+        assert_eq!(
+            manager.apply_schema("articles", &item()).unwrap(),
+            serde_json::json!({ "id": 1, "title": "Launch" })
+        );
+        let processed = manager.apply_schema("articles", &page()).unwrap();
+        assert_eq!(
+            processed["results"],
+            serde_json::json!([{ "id": 1, "title": "Launch" }, { "id": 2, "title": "Landing" }])
+        );
+        // The pagination envelope is not an item
+        assert_eq!(processed["count"], 2);
+    }
+
+    #[test]
+    fn error_rejects_unknown_fields() {
+        let manager = with_policy("error");
+        for data in [item(), page()] {
+            let error = manager.apply_schema("articles", &data).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Schema 'articles' does not declare field(s): extra"
+            );
+        }
+        let clean = serde_json::json!({ "id": 2, "title": "Landing" });
+        assert_eq!(manager.apply_schema("articles", &clean).unwrap(), clean);
+    }
+
+    const NESTED: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+        title = "String"
+
+        [articles.schema.nested_fields]
+        authors = "Vec<authors>"
+        launches = "Vec<Launch>"
+
+        [authors]
+        url = "https://example.com/authors"
+
+        [authors.schema]
+        name = "String"
+    "#;
+
+    fn raw_article(id: u32) -> Value {
+        serde_json::json!({
+            "id": id,
+            "title": "Launch",
+            "summary": "undeclared",
+            "authors": [{ "name": "Ada", "socials": { "x": "@ada" } }],
+            "launches": [{ "launch_id": "abc", "provider": "Launch Library 2" }],
+        })
+    }
+
+    fn projected_article(id: u32) -> Value {
+        serde_json::json!({
+            "id": id,
+            "title": "Launch",
+            "authors": [{ "name": "Ada" }],
+            "launches": [{ "launch_id": "abc", "provider": "Launch Library 2" }],
+        })
+    }
+
+    #[test]
+    fn project_drops_undeclared_fields_of_an_item() {
+        let manager = manager(NESTED);
+        assert_eq!(
+            manager.project("articles", &raw_article(1)).unwrap(),
+            projected_article(1)
+        );
+    }
+
+    #[test]
+    fn project_drops_undeclared_fields_of_each_result() {
+        let manager = manager(NESTED);
+        let page = serde_json::json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [raw_article(1), raw_article(2)],
+        });
+        assert_eq!(
+            manager.project("articles", &page).unwrap(),
+            serde_json::json!({
+                "count": 2,
+                "next": null,
+                "previous": null,
+                "results": [projected_article(1), projected_article(2)],
+            })
+        );
+    }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn declared_order_follows_the_schema() {
+        let mut manager = manager(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+
+            [articles.schema]
+            title = "String"
+            id = "u32"
+            url = "String"
+
+            [articles.schema.nested_fields]
+            authors = "Vec<Author>"
+
+            [articles.computed_fields]
+            domain = { from = "url", op = "host" }
+            "#,
+        );
+        manager.set_declared_order(true);
+        let data: Value = serde_json::from_str(
+            r#"{"extra":1,"url":"https://example.com/a","authors":[],"id":7,"title":"T"}"#,
+        )
+        .unwrap();
+
+        let processed = manager.apply_schema("articles", &data).unwrap();
+        assert_eq!(
+            serde_json::to_string(&processed).unwrap(),
+            r#"{"title":"T","id":7,"url":"https://example.com/a","authors":[],"domain":"example.com","extra":1}"#
+        );
+    }
+
+    const COMPUTED: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+        title = "String"
+        url = "String"
+
+        [articles.schema.nested_fields]
+        launches = "Vec<Launch>"
+        events = "Vec<Event>"
+
+        [articles.computed_fields]
+        domain = { from = "url", op = "host" }
+        title_length = { from = "title", op = "length" }
+    "#;
+
+    /// An article as the API sends it, with renamed launch and event IDs
+    fn upstream_article(id: u32) -> Value {
+        serde_json::json!({
+            "id": id,
+            "title": "Starship flight test",
+            "url": "https://example.com/starship",
+            "image_url": "https://example.com/starship.jpg",
+            "news_site": "Example News",
+            "summary": "The booster was caught.",
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T12:30:00Z",
+            "featured": false,
+            "authors": [{ "name": "Ada", "socials": null }],
+            "launches": [{ "launch_id": "e3df2ecd", "provider": "Launch Library 2" }],
+            "events": [{ "event_id": 842, "provider": "Launch Library 2" }],
+        })
+    }
+
+    #[test]
+    fn upstream_json_drops_computed_fields() {
+        let manager = manager(COMPUTED);
+        let schema = manager.get_schema("articles").unwrap();
+        let page = serde_json::json!({
+            "count": 2,
+            "next": null,
+            "previous": null,
+            "results": [upstream_article(1), upstream_article(2)],
+        });
+        for data in [upstream_article(1), page] {
+            let processed = manager.apply_schema("articles", &data).unwrap();
+            assert_ne!(processed, data);
+            assert_eq!(schema.to_upstream_json(&processed), data);
+        }
+    }
+
+    #[test]
+    fn upstream_json_round_trips_through_renamed_fields() {
+        use crate::endpoints::article::Article;
+
+        let manager = manager(COMPUTED);
+        let schema = manager.get_schema("articles").unwrap();
+        let upstream = upstream_article(1);
+
+        // parse -> transform -> reverse -> parse again
+        let article: Article = serde_json::from_value(upstream.clone()).unwrap();
+        let processed = manager
+            .apply_schema("articles", &serde_json::to_value(&article).unwrap())
+            .unwrap();
+        assert_eq!(processed["launches"][0]["launch_id"], "e3df2ecd");
+        assert_eq!(processed["domain"], "example.com");
+
+        let reversed = schema.to_upstream_json(&processed);
+        assert_eq!(reversed, upstream);
+        let reparsed: Article = serde_json::from_value(reversed).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), upstream);
+    }
+
+    #[test]
+    fn upstream_json_keeps_computed_fields_that_replaced_declared_ones() {
+        let manager = manager(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+
+            [articles.schema]
+            title = "String"
+
+            [articles.computed_fields]
+            title = { from = "title", op = "lowercase" }
+            "#,
+        );
+        let processed = manager
+            .apply_schema("articles", &serde_json::json!({ "title": "Starship" }))
+            .unwrap();
+        let schema = manager.get_schema("articles").unwrap();
+        assert_eq!(
+            schema.to_upstream_json(&processed),
+            serde_json::json!({ "title": "starship" })
+        );
+    }
+
+    const LIMITED: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+
+        [articles.query_params]
+        limit = 10
+        offset = 0
+    "#;
+
+    fn limit_query(manager: &SchemaManager, limit: &str) -> Result<String, String> {
+        manager
+            .build_query_string("articles", &params(&[("limit", limit)]))
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn limit_is_clamped_at_the_maximum() {
+        let mut manager = manager(LIMITED);
+        assert_eq!(limit_query(&manager, "1").unwrap(), "?limit=1&offset=0");
+        assert_eq!(limit_query(&manager, "100").unwrap(), "?limit=100&offset=0");
+        assert_eq!(limit_query(&manager, "101").unwrap(), "?limit=100&offset=0");
+        assert_eq!(limit_query(&manager, "500").unwrap(), "?limit=100&offset=0");
+
+        manager.set_max_limit(50);
+        assert_eq!(limit_query(&manager, "50").unwrap(), "?limit=50&offset=0");
+        assert_eq!(limit_query(&manager, "51").unwrap(), "?limit=50&offset=0");
+    }
+
+    #[test]
+    fn zero_max_limit_is_raised_to_one() {
+        let mut manager = manager(LIMITED);
+        manager.set_max_limit(0);
+        assert_eq!(manager.max_limit(), 1);
+        assert_eq!(limit_query(&manager, "20").unwrap(), "?limit=1&offset=0");
+    }
+
+    #[test]
+    fn limit_below_one_is_rejected() {
+        let manager = manager(LIMITED);
+        for limit in ["0", "-1", "ten"] {
+            assert_eq!(
+                limit_query(&manager, limit).unwrap_err(),
+                format!("Invalid limit '{}': expected a positive integer", limit)
+            );
+        }
+    }
+
+    #[test]
+    fn floats_drop_rounding_artifacts() {
+        let cases = [
+            (0.1 + 0.2, "0.3"),
+            (0.3, "0.3"),
+            (2.5, "2.5"),
+            (10.0, "10"),
+            (0.0, "0"),
+            (-0.0, "0"),
+            (-0.0000004, "-0.0000004"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                QueryParamValue::Float(value).format(DEFAULT_FLOAT_PRECISION),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn small_floats_keep_significant_digits() {
+        let format = |value: f64, precision| QueryParamValue::Float(value).format(precision);
+        assert_eq!(format(1e-7, 6), "0.0000001");
+        assert_eq!(format(1.23456789e-7, 6), "0.000000123457");
+        assert_eq!(format(3.0000000000000004e-8, 6), "0.00000003");
+        assert_eq!(format(0.4, 0), "0.4");
+        assert_eq!(format(12.34567, 2), "12.35");
+        // Values that keep a digit at the configured precision are rounded
+        assert_eq!(format(0.0000016, 6), "0.000002");
+    }
+
+    #[test]
+    fn float_params_follow_set_float_precision() {
+        let mut manager = manager(SEARCH);
+        manager.set_float_precision(2);
+        let query = |value: f64| {
+            let params = HashMap::from([("search".to_string(), QueryParamValue::Float(value))]);
+            manager
+                .build_query_string_from_values("articles", &params)
+                .unwrap()
+        };
+        assert_eq!(query(12.34567), "?search=12.35");
+        assert_eq!(query(0.001), "?search=0.001");
+    }
+
+    #[test]
+    fn openapi_type_names_stop_at_ref_cycles() {
+        let spec = serde_json::json!({
+            "components": {"schemas": {
+                "Loop": {"allOf": [{"$ref": "#/components/schemas/Loop"}]},
+                "Nested": {"type": "array", "items": {"$ref": "#/components/schemas/Nested"}},
+                "Ping": {"allOf": [{"$ref": "#/components/schemas/Pong"}]},
+                "Pong": {"allOf": [{"$ref": "#/components/schemas/Ping"}]},
+                "Count": {"type": "integer"},
+                "Counts": {"type": "array", "items": {"$ref": "#/components/schemas/Count"}},
+            }}
+        });
+        let type_name = |name: &str| {
+            let property = serde_json::json!({"$ref": format!("#/components/schemas/{name}")});
+            openapi_type_name(&spec, &property)
+        };
+        assert_eq!(type_name("Loop"), "String");
+        assert_eq!(type_name("Nested"), "Vec<String>");
+        assert_eq!(type_name("Ping"), "String");
+        // A reference reached twice without a cycle still resolves
+        assert_eq!(type_name("Counts"), "Vec<i64>");
+        let pair = serde_json::json!({
+            "type": "array",
+            "items": {"type": "array", "items": {"$ref": "#/components/schemas/Count"}}
+        });
+        assert_eq!(openapi_type_name(&spec, &pair), "Vec<Vec<i64>>");
+    }
+
+    const MANY_PARAMS: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+
+        [articles.query_params]
+        title_contains = "x"
+        news_site = "x"
+        search = "x"
+        ordering = "x"
+        is_featured = "x"
+        has_launch = "x"
+    "#;
+
+    #[test]
+    fn query_strings_are_sorted_whatever_the_map_order() {
+        let manager = manager(MANY_PARAMS);
+        let pairs = [
+            ("title_contains", "mars"),
+            ("news_site", "NASA"),
+            ("search", "rover"),
+            ("ordering", "-published_at"),
+            ("is_featured", "true"),
+            ("has_launch", "false"),
+        ];
+        let expected = "?has_launch=false&is_featured=true&news_site=NASA\
+                        &ordering=-published_at&search=rover&title_contains=mars";
+
+        // Every map is seeded differently, so iteration order varies
+        for round in 0..32 {
+            let mut query = HashMap::new();
+            let ordered: Vec<_> = if round % 2 == 0 {
+                pairs.iter().collect()
+            } else {
+                pairs.iter().rev().collect()
+            };
+            for (key, value) in ordered {
+                query.insert(key.to_string(), value.to_string());
+            }
+            assert_eq!(
+                manager.build_query_string("articles", &query).unwrap(),
+                expected
+            );
+        }
+    }
+
+    const DESCRIBED: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+        title = "String"
+
+        [articles.schema.nested_fields]
+        launches = "launches"
+        authors = "authors"
+
+        [articles.query_params]
+        limit = 10
+        search = { default = "", description = "Full-text search in titles" }
+        news_site = { default = ["NASA"], array_format = "repeat" }
+    "#;
+
+    #[test]
+    fn describe_reports_toml_descriptions_and_nested_fields() {
+        let descriptions = manager(DESCRIBED).describe();
+        assert_eq!(descriptions.len(), 1);
+        let articles = &descriptions[0];
+        assert_eq!(articles.name, "articles");
+        let fields: Vec<&str> = articles.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, ["id", "title"]);
+        assert_eq!(
+            articles.nested_fields,
+            [
+                ("authors".to_string(), "authors".to_string()),
+                ("launches".to_string(), "launches".to_string())
+            ]
+        );
+
+        let params: Vec<(&str, Option<&str>)> = articles
+            .query_params
+            .iter()
+            .map(|param| (param.name.as_str(), param.description.as_deref()))
+            .collect();
+        assert_eq!(
+            params,
+            [
+                ("limit", None),
+                ("news_site", None),
+                ("search", Some("Full-text search in titles"))
+            ]
+        );
+
+        let text = articles.to_string();
+        assert!(
+            text.contains("  Nested fields:\n    authors: authors\n"),
+            "{text}"
+        );
+        assert!(text.contains(" - Full-text search in titles\n"), "{text}");
+    }
+
+    const TYPED: &str = r#"
+        [articles]
+        url = "https://example.com/articles"
+
+        [articles.schema]
+        id = "u32"
+        slug = "slug"
+        tags = "Vec<slug>"
+        published_at = "datetime"
+        link = "Option<url>"
+    "#;
+
+    /// A manager for `TYPED` with a `slug` type of lowercase words and dashes
+    fn with_slug_type() -> SchemaManager {
+        let mut manager = manager(TYPED);
+        manager.register_type("slug", |value| {
+            let text = value.as_str().ok_or("expected a string")?;
+            if !text.is_empty() && text.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+                Ok(())
+            } else {
+                Err(format!("'{}' is not a slug", text))
+            }
+        });
+        manager
+    }
+
+    #[test]
+    fn registered_types_accept_valid_values() {
+        let manager = with_slug_type();
+        let data = serde_json::json!({
+            "id": 1,
+            "slug": "launch-recap",
+            "tags": ["falcon", "starlink"],
+            "published_at": "2026-10-01T12:00:00+02:00",
+            "link": null,
+        });
+        assert_eq!(manager.apply_schema("articles", &data).unwrap(), data);
+        // Missing fields are not checked
+        let sparse = serde_json::json!({ "id": 2 });
+        assert_eq!(manager.apply_schema("articles", &sparse).unwrap(), sparse);
+    }
+
+    #[test]
+    fn registered_types_reject_invalid_values() {
+        let manager = with_slug_type();
+        let cases = [
+            (
+                serde_json::json!({ "slug": "Launch Recap" }),
+                "Schema 'articles' field 'slug' (slug): 'Launch Recap' is not a slug",
+            ),
+            (
+                serde_json::json!({ "tags": ["falcon", 9] }),
+                "Schema 'articles' field 'tags' (slug): expected a string",
+            ),
+            (
+                serde_json::json!({ "published_at": "yesterday" }),
+                "Schema 'articles' field 'published_at' (datetime): 'yesterday' is not an RFC 3339 timestamp",
+            ),
+            (
+                serde_json::json!({ "link": "example.com/recap" }),
+                "Schema 'articles' field 'link' (url): 'example.com/recap' is not an absolute http(s) URL",
+            ),
+        ];
+        for (data, expected) in cases {
+            let error = manager.apply_schema("articles", &data).unwrap_err();
+            assert!(error.to_string().starts_with(expected), "{data}: {error}");
+        }
+    }
+
+    #[test]
+    fn registering_a_type_again_replaces_its_validator() {
+        let mut manager = with_slug_type();
+        manager.register_type("slug", |_| Ok(()));
+        let data = serde_json::json!({ "slug": "Anything Goes" });
+        assert_eq!(manager.apply_schema("articles", &data).unwrap(), data);
+    }
+
+    /// A query parameter definition of `param_type` defaulting to `default`
+    fn param(name: &str, param_type: &str, default: QueryParamValue) -> QueryParamDefinition {
+        QueryParamDefinition {
+            name: name.to_string(),
+            param_type: param_type.to_string(),
+            default: Some(default),
+            description: None,
+            array_format: ArrayFormat::default(),
+        }
+    }
+
+    #[test]
+    fn validate_schemas_reports_every_problem() {
+        let mut manager = manager(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+
+            [articles.schema]
+            id = "u32"
+            title = "Strnig"
+            published_at = "datetime"
+
+            [articles.schema.nested_fields]
+            launches = "Vec<Launch>"
+            author = "Writer"
+
+            [articles.query_params]
+            limit = 10
+            news_site = ["NASA", "ESA"]
+
+            [articles.computed_fields]
+            domain = { from = "link", op = "host" }
+            "#,
+        );
+        let query_params = &mut manager.schemas.get_mut("articles").unwrap().query_params;
+        query_params.insert(
+            "offset".to_string(),
+            param("offset", "u32", QueryParamValue::Integer(-1)),
+        );
+        query_params.insert(
+            "has_launch".to_string(),
+            param(
+                "has_launch",
+                "bool",
+                QueryParamValue::String("maybe".to_string()),
+            ),
+        );
+        query_params.insert(
+            "ids".to_string(),
+            param(
+                "ids",
+                "Vec<i64>",
+                QueryParamValue::Array(vec![
+                    QueryParamValue::Integer(1),
+                    QueryParamValue::Float(1.5),
+                ]),
+            ),
+        );
+        query_params.insert(
+            "since".to_string(),
+            param(
+                "since",
+                "datetime",
+                QueryParamValue::String("today".to_string()),
+            ),
+        );
+
+        let problems: Vec<String> = manager
+            .validate_schemas()
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "articles.title: unknown field type 'Strnig'",
+                "articles.author: nested type 'Writer' is not a known schema or type",
+                "articles.query_params.has_launch: default 'maybe' is not a valid 'bool'",
+                "articles.query_params.ids: default '1,1.5' is not a valid 'Vec<i64>'",
+                "articles.query_params.offset: default '-1' is not a valid 'u32'",
+                "articles.query_params.since: invalid type 'datetime'",
+                "articles.computed_fields.domain: source field 'link' is not declared",
+            ]
+        );
+    }
+
+    #[test]
+    fn defaults_fit_their_types() {
+        use QueryParamValue::*;
+        assert!(default_fits("String", &Integer(5)));
+        assert!(default_fits("f64", &Integer(5)));
+        assert!(default_fits("Option<u8>", &Integer(255)));
+        assert!(!default_fits("u8", &Integer(256)));
+        assert!(default_fits("Vec<String>", &String("NASA".to_string())));
+        assert!(default_fits("Vec<bool>", &Array(vec![Boolean(true)])));
+        assert!(!default_fits("i64", &Array(vec![Integer(1)])));
+        assert!(!default_fits("i32", &Float(1.5)));
+    }
+
+    #[test]
+    fn valid_schemas_report_nothing() {
+        assert_eq!(manager(SEARCH).validate_schemas(), Ok(()));
+    }
+
+    #[test]
+    fn default_params_resolve_each_kind_of_default() {
+        let mut manager = manager(
+            r#"
+            [articles]
+            url = "https://example.com/articles"
+
+            [articles.schema]
+            id = "u32"
+
+            [articles.query_params]
+            ordering = "-published_at"
+            is_featured = true
+            limit = 500
+            offset = 20
+            min_score = 0.123456789
+            news_site__in = ["NASA", "SpaceNews"]
+            news_site = { default = ["NASA", "ESA"], array_format = "repeat" }
+            search = { description = "No default, so not included" }
+            "#,
+        );
+        manager.set_max_limit(50);
+        manager.set_float_precision(3);
+
+        let defaults = manager.default_params("articles").unwrap();
+        assert_eq!(
+            defaults,
+            params(&[
+                ("ordering", "-published_at"),
+                ("is_featured", "true"),
+                // Clamped to `max_limit`, like `build_query_string` does
+                ("limit", "50"),
+                ("offset", "20"),
+                ("min_score", "0.123"),
+                ("news_site__in", "NASA,SpaceNews"),
+                // Joined with commas whatever the array format
+                ("news_site", "NASA,ESA"),
+            ])
+        );
+
+        assert_eq!(
+            manager.default_params("missing").unwrap_err().to_string(),
+            "Schema 'missing' not found"
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Pluggable destinations for executor results
+//!
+//! The executor hands every successful response to a [`ContentSink`]. The
+//! default [`StdoutSink`] prints results to the terminal, while
+//! [`FileSink`] appends them to a newline-delimited JSON file, optionally
+//! gzip-compressed. Implement the
+//! trait yourself to forward results to a queue, webhook or database.
+
+use async_trait::async_trait;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::Value;
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+/// Error type returned by sinks
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A destination for data fetched by the executor
+#[async_trait]
+pub trait ContentSink: Send + Sync {
+    /// Write the processed response of an endpoint
+    async fn write(&self, endpoint: &str, data: &Value) -> Result<(), BoxError>;
+}
+
+/// Sink that prints results to stdout
+#[derive(Debug, Clone)]
+pub struct StdoutSink {
+    /// Output format: "json", "table", "compact", or "detailed"
+    output_format: String,
+    /// Maximum number of items to display per endpoint
+    max_display_items: usize,
+}
+
+impl StdoutSink {
+    /// Create a new stdout sink
+    pub fn new(output_format: impl Into<String>, max_display_items: usize) -> Self {
+        Self {
+            output_format: output_format.into(),
+            max_display_items,
+        }
+    }
+
+    /// Display results based on the configured output format
+    fn display_results(&self, data: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        match self.output_format.as_str() {
+            "json" => {
+                println!("{}", serde_json::to_string_pretty(data)?);
+            }
+            "table" => {
+                self.display_as_table(data)?;
+            }
+            "compact" => {
+                self.display_compact(data);
+            }
+            _ => {
+                self.display_detailed(data)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Display results in detailed format
+    fn display_detailed(&self, data: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(obj) = data.as_object() {
+            if obj.contains_key("results") && obj.contains_key("count") {
+                // Paginated response
+                if let Some(count) = obj.get("count").and_then(|v| v.as_u64()) {
+                    println!("  Total results: {}", count);
+                }
+
+                if let Some(results) = obj.get("results").and_then(|v| v.as_array()) {
+                    println!(
+                        "  Displaying first {} items:",
+                        std::cmp::min(results.len(), self.max_display_items)
+                    );
+
+                    for (i, item) in results.iter().take(self.max_display_items).enumerate() {
+                        println!("    Item {}:", i + 1);
+                        self.display_object(item, 6)?;
+                    }
+                }
+            } else {
+                // Single object
+                println!("  Response:");
+                self.display_object(data, 4)?;
+            }
+        } else {
+            println!("  Response: {:?}", data);
+        }
+
+        Ok(())
+    }
+
+    /// Display a JSON object with indentation
+    fn display_object(
+        &self,
+        value: &Value,
+        indent: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let indent_str = " ".repeat(indent);
+
+        if let Some(obj) = value.as_object() {
+            for (key, val) in obj {
+                match val {
+                    Value::Object(_) => {
+                        println!("{}{}:", indent_str, key);
+                        self.display_object(val, indent + 2)?;
+                    }
+                    Value::Array(arr) => {
+                        println!("{}{}: [{} items]", indent_str, key, arr.len());
+                        if !arr.is_empty()
+                            && key != "events"
+                            && key != "launches"
+                            && let Some(first) = arr.first()
+                        {
+                            if first.is_object() {
+                                println!("{}  First item:", indent_str);
+                                self.display_object(first, indent + 4)?;
+                            } else {
+                                println!("{}  First item: {:?}", indent_str, first);
+                            }
+                        }
+                    }
+                    Value::Null => {
+                        println!("{}{}: N/A", indent_str, key);
+                    }
+                    _ => {
+                        println!("{}{}: {}", indent_str, key, val);
+                    }
+                }
+            }
+        } else {
+            println!("{}{:?}", indent_str, value);
+        }
+
+        Ok(())
+    }
+
+    /// Display one tab-separated line per result with its key fields
+    fn display_compact(&self, data: &Value) {
+        let items = match data.get("results").and_then(|v| v.as_array()) {
+            Some(results) => results.as_slice(),
+            None => std::slice::from_ref(data),
+        };
+
+        for item in items.iter().take(self.max_display_items) {
+            let fields: Vec<String> = ["id", "title", "news_site", "published_at"]
+                .iter()
+                .map(|key| match item.get(*key) {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(Value::Null) | None => "N/A".to_string(),
+                    Some(other) => other.to_string(),
+                })
+                .collect();
+            println!("  {}", fields.join("\t"));
+        }
+    }
+
+    /// Display results in table format
+    fn display_as_table(&self, data: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(obj) = data.as_object()
+            && let Some(results) = obj.get("results").and_then(|v| v.as_array())
+        {
+            println!(
+                "  | {:<30} | {:<20} | {:<20} |",
+                "Title", "News Site", "Published"
+            );
+            println!("  |{:-<32}|{:-<22}|{:-<22}|", "", "", "");
+
+            for item in results.iter().take(self.max_display_items) {
+                if let Some(item_obj) = item.as_object() {
+                    let title = item_obj
+                        .get("title")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("N/A");
+                    let news_site = item_obj
+                        .get("news_site")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("N/A");
+                    let published = item_obj
+                        .get("published_at")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("N/A");
+
+                    // Truncate long titles
+                    let title_truncated = if title.len() > 27 {
+                        format!("{}...", &title[..27])
+                    } else {
+                        title.to_string()
+                    };
+
+                    println!(
+                        "  | {:<30} | {:<20} | {:<20} |",
+                        title_truncated,
+                        news_site,
+                        &published[..std::cmp::min(20, published.len())]
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for StdoutSink {
+    fn default() -> Self {
+        Self::new("detailed", 10)
+    }
+}
+
+#[async_trait]
+impl ContentSink for StdoutSink {
+    async fn write(&self, _endpoint: &str, data: &Value) -> Result<(), BoxError> {
+        self.display_results(data)
+            .map_err(|e| BoxError::from(e.to_string()))
+    }
+}
+
+/// Sink that appends results to a file as newline-delimited JSON
+///
+/// Each line is an object of the form `{"endpoint": ..., "data": ...}`.
+#[derive(Debug, Clone)]
+pub struct FileSink {
+    /// Path of the output file
+    path: PathBuf,
+    /// Whether records are gzip-compressed
+    gzip: bool,
+}
+
+impl FileSink {
+    /// Create a sink appending to the file at `path`, creating it if needed
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            gzip: false,
+        }
+    }
+
+    /// Gzip-compress the output, e.g. for an `.ndjson.gz` archive
+    ///
+    /// Each write appends a complete gzip member, trailer included, so the
+    /// file stays valid even if the process stops between writes. `gunzip`
+    /// and `zcat` read the concatenated members as one stream; with
+    /// `flate2`, use `MultiGzDecoder` rather than `GzDecoder`.
+    pub fn with_gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+}
+
+#[async_trait]
+impl ContentSink for FileSink {
+    async fn write(&self, endpoint: &str, data: &Value) -> Result<(), BoxError> {
+        let record = serde_json::json!({ "endpoint": endpoint, "data": data });
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        if self.gzip {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&line)?;
+            line = encoder.finish()?;
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::MultiGzDecoder;
+    use serde_json::json;
+    use std::io::Read;
+
+    fn records(text: &str) -> Vec<Value> {
+        text.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn file_sink_appends_one_json_line_per_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.ndjson");
+        let sink = FileSink::new(&path);
+
+        sink.write("launches", &json!({"count": 1})).await.unwrap();
+        sink.write("events", &json!([1, 2])).await.unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            records(&text),
+            vec![
+                json!({"endpoint": "launches", "data": {"count": 1}}),
+                json!({"endpoint": "events", "data": [1, 2]}),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn gzip_file_sink_writes_members_readable_as_one_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.ndjson.gz");
+        let sink = FileSink::new(&path).with_gzip(true);
+
+        sink.write("launches", &json!({"count": 1})).await.unwrap();
+        sink.write("events", &json!({"count": 2})).await.unwrap();
+
+        let compressed = std::fs::read(&path).unwrap();
+        let mut text = String::new();
+        MultiGzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(
+            records(&text),
+            vec![
+                json!({"endpoint": "launches", "data": {"count": 1}}),
+                json!({"endpoint": "events", "data": {"count": 2}}),
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Custom deserializers for fields whose JSON representation varies
+//! between SpaceDevs endpoints and API versions
+
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use std::fmt;
+
+/// Deserialize a `u32` ID that may arrive as a JSON number or a numeric string
+///
+/// Use with `#[serde(deserialize_with = "flexible_u32")]`.
+pub fn flexible_u32<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct FlexibleU32;
+
+    impl Visitor<'_> for FlexibleU32 {
+        type Value = u32;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a non-negative integer or a numeric string")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u32, E> {
+            u32::try_from(value).map_err(|_| E::invalid_value(Unexpected::Unsigned(value), &self))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<u32, E> {
+            u32::try_from(value).map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u32, E> {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(FlexibleU32)
+}
+
+/// Deserialize a boolean that may arrive as a JSON bool, `0`/`1`, or a
+/// string such as `"true"`, `"False"`, `"1"` or `"no"`
+///
+/// `null` reads as `false`. Use with
+/// `#[serde(default, deserialize_with = "flexible_bool")]`.
+pub fn flexible_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct FlexibleBool;
+
+    impl Visitor<'_> for FlexibleBool {
+        type Value = bool;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a boolean, 0 or 1, or a boolean string")
+        }
+
+        fn visit_bool<E: de::Error>(self, value: bool) -> Result<bool, E> {
+            Ok(value)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<bool, E> {
+            match value {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+            }
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<bool, E> {
+            match value {
+                0 => Ok(false),
+                1 => Ok(true),
+                _ => Err(E::invalid_value(Unexpected::Signed(value), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<bool, E> {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" => Ok(true),
+                "false" | "0" | "no" | "" => Ok(false),
+                _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+            }
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<bool, E> {
+            Ok(false)
+        }
+    }
+
+    deserializer.deserialize_any(FlexibleBool)
+}
+
+/// Deserialize an optional pagination link that may be a URL or a bare
+/// page number
+///
+/// Use with `#[serde(default, deserialize_with = "optional_link")]` on
+/// `next`/`previous`; numbers are kept as their decimal string.
+pub fn optional_link<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Link {
+        Url(String),
+        Page(u64),
+    }
+
+    Ok(
+        Option::<Link>::deserialize(deserializer)?.map(|link| match link {
+            Link::Url(url) => url,
+            Link::Page(page) => page.to_string(),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct Flag {
+        #[serde(default, deserialize_with = "flexible_bool")]
+        featured: bool,
+    }
+
+    fn featured(value: serde_json::Value) -> Result<bool, serde_json::Error> {
+        serde_json::from_value::<Flag>(json!({ "featured": value })).map(|flag| flag.featured)
+    }
+
+    #[test]
+    fn flexible_bool_accepts_boolean_forms() {
+        let accepted = [
+            (json!(true), true),
+            (json!(false), false),
+            (json!(1), true),
+            (json!(0), false),
+            (json!("true"), true),
+            (json!("False"), false),
+            (json!(" TRUE "), true),
+            (json!("1"), true),
+            (json!("0"), false),
+            (json!("yes"), true),
+            (json!("no"), false),
+            (json!(""), false),
+            (json!(null), false),
+        ];
+        for (value, expected) in accepted {
+            assert_eq!(featured(value.clone()).unwrap(), expected, "{}", value);
+        }
+        let missing: Flag = serde_json::from_value(json!({})).unwrap();
+        assert!(!missing.featured);
+    }
+
+    #[test]
+    fn flexible_bool_rejects_other_values() {
+        for value in [
+            json!(2),
+            json!(-1),
+            json!(0.5),
+            json!("maybe"),
+            json!("2"),
+            json!([true]),
+            json!({ "value": true }),
+        ] {
+            assert!(featured(value.clone()).is_err(), "{}", value);
+        }
+    }
+}
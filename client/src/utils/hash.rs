@@ -0,0 +1,110 @@
+//! Stable hashing of content for change detection
+
+use crate::endpoints::author::Author;
+
+/// 64-bit FNV-1a, whose output is fixed by its definition
+///
+/// `std`'s `DefaultHasher` may change between Rust releases, so it cannot
+/// be used for hashes that are stored and compared across runs.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Hash a string with its length first, so field boundaries are unambiguous
+    fn str(&mut self, value: &str) {
+        self.bytes(&(value.len() as u64).to_le_bytes());
+        self.bytes(value.as_bytes());
+    }
+}
+
+/// Hash the fields that make up an item's content
+///
+/// Backs the `content_hash` methods of articles, blogs and reports. The
+/// hash changes only when one of these fields does, unlike `updated_at`,
+/// and is the same across runs and platforms, so it can be stored to skip
+/// rewriting unchanged items.
+///
+/// Feeds, in order: `title`, `summary` (a missing summary hashes
+/// differently from an empty one), `url`, then each author's `name` in
+/// the order given. Author socials, timestamps, `featured`, `image_url`,
+/// `news_site` and linked launches/events are not included. Fields are
+/// hashed exactly as received; call the item's `normalize` first to
+/// ignore whitespace-only edits.
+pub(crate) fn content_hash(
+    title: &str,
+    summary: Option<&str>,
+    url: &str,
+    authors: &[Author],
+) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher.str(title);
+    match summary {
+        Some(summary) => {
+            hasher.bytes(&[1]);
+            hasher.str(summary);
+        }
+        None => hasher.bytes(&[0]),
+    }
+    hasher.str(url);
+    hasher.bytes(&(authors.len() as u64).to_le_bytes());
+    for author in authors {
+        hasher.str(&author.name);
+    }
+    hasher.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn author(name: &str) -> Author {
+        Author {
+            name: name.to_string(),
+            socials: None,
+        }
+    }
+
+    #[test]
+    fn hash_is_fixed_by_the_fields() {
+        let authors = [author("Ada")];
+        let hash = content_hash("Title", Some("Summary"), "https://a.test", &authors);
+        assert_eq!(
+            hash,
+            content_hash("Title", Some("Summary"), "https://a.test", &authors)
+        );
+        assert_ne!(
+            hash,
+            content_hash("Title ", Some("Summary"), "https://a.test", &authors)
+        );
+        assert_ne!(
+            hash,
+            content_hash("Title", Some("Summary"), "https://a.test", &[])
+        );
+    }
+
+    #[test]
+    fn field_boundaries_and_missing_summaries_are_distinct() {
+        assert_ne!(
+            content_hash("ab", Some("c"), "", &[]),
+            content_hash("a", Some("bc"), "", &[])
+        );
+        assert_ne!(
+            content_hash("a", None, "", &[]),
+            content_hash("a", Some(""), "", &[])
+        );
+        assert_ne!(
+            content_hash("a", None, "", &[author("B"), author("C")]),
+            content_hash("a", None, "", &[author("C"), author("B")])
+        );
+    }
+}
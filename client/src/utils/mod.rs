@@ -1 +1,4 @@
-pub mod urls;
\ No newline at end of file
+pub mod deserialize;
+pub(crate) mod hash;
+pub mod text;
+pub mod urls;
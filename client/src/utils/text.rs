@@ -0,0 +1,6 @@
+//! Text helpers shared by the content types
+
+/// Trim a string and collapse every internal run of whitespace to one space
+pub fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -1,3 +1,17 @@
 // Base URL for the SpaceFlight News API
 pub const SPACEFLIGHT_NEWS_API_BASE: &str = "https://api.spaceflightnewsapi.net/v4";
 pub const SPACEDEVS_DATA_API_BASE: &str = "https://ll.thespacedevs.com/2.3.0";
+
+/// Extract the host from an absolute URL, without scheme, credentials or port
+pub fn host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => authority,
+    };
+    (!host.is_empty()).then_some(host)
+}
@@ -0,0 +1,104 @@
+//! Fetch articles and store them in a local SQLite database
+//!
+//! Usage: `cargo run -p dal --example ingest_sqlite -- [path/to/spacedevs.db]`
+//!
+//! Set `SPACEDEVS_API_BASE` to fetch from a mirror instead of the public
+//! API. Runs the migrations, fetches a page of articles, inserts them with
+//! their authors, launches and events plus the join rows, then reads them
+//! back. Runs are idempotent: articles already stored and unchanged are
+//! skipped, and updated articles have their links brought up to date.
+
+use client::SpaceDevsClient;
+use client::article::Article;
+use dal::content::list_articles;
+use dal::ingest::{FieldMap, article_active_model, filter_new};
+use dal::relations::sync_article_relations;
+use entities::{articles, authors};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{Database, DatabaseConnection, DbErr, EntityTrait, ModelTrait};
+use std::collections::HashMap;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "spacedevs.db".to_string());
+    let db = Database::connect(format!("sqlite://{}?mode=rwc", path)).await?;
+    Migrator::up(&db, None).await?;
+
+    let client = match std::env::var("SPACEDEVS_API_BASE") {
+        Ok(base_url) => SpaceDevsClient::with_base_url(base_url),
+        Err(_) => SpaceDevsClient::new(),
+    };
+    let params = HashMap::from([("limit".to_string(), "10".to_string())]);
+    let page = client.get_articles(&params).await?;
+
+    let new_articles = filter_new(&db, &page.results).await?;
+    println!(
+        "Fetched {} articles, {} new or updated",
+        page.results.len(),
+        new_articles.len()
+    );
+
+    let field_map = FieldMap::new();
+    for article in &new_articles {
+        store_article(&db, article, &field_map).await?;
+    }
+
+    let (stored, total) = list_articles(&db, 0, 10).await?;
+    println!("\n{} articles in {}, newest first:", total, path);
+    for article in stored {
+        let names: Vec<String> = article
+            .find_related(authors::Entity)
+            .all(&db)
+            .await?
+            .into_iter()
+            .map(|author| author.name)
+            .collect();
+        println!(
+            "  [{}] {} ({}) by {}",
+            article.id,
+            article.title,
+            article.news_site.as_deref().unwrap_or("unknown site"),
+            if names.is_empty() {
+                "unknown".to_string()
+            } else {
+                names.join(", ")
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Insert or update an article, its authors, launches and events, and the
+/// join rows
+async fn store_article(
+    db: &DatabaseConnection,
+    article: &Article,
+    field_map: &FieldMap,
+) -> Result<(), DbErr> {
+    use articles::Column;
+
+    articles::Entity::insert(article_active_model(article, field_map)?)
+        .on_conflict(
+            OnConflict::column(Column::Id)
+                .update_columns([
+                    Column::Title,
+                    Column::Url,
+                    Column::ImageUrl,
+                    Column::NewsSite,
+                    Column::Summary,
+                    Column::PublishedAt,
+                    Column::UpdatedAt,
+                    Column::Featured,
+                ])
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+    sync_article_relations(db, article.id as i32, article).await?;
+    Ok(())
+}
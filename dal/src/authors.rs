@@ -0,0 +1,118 @@
+//! Reads of the authors directory
+
+use client::author::Author;
+use client::social::Social;
+use entities::{author_socials, authors};
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QueryOrder};
+
+/// An author row together with its optional socials row
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthorRecord(pub authors::Model, pub Option<author_socials::Model>);
+
+impl From<(authors::Model, Option<author_socials::Model>)> for AuthorRecord {
+    fn from((author, socials): (authors::Model, Option<author_socials::Model>)) -> Self {
+        Self(author, socials)
+    }
+}
+
+impl From<AuthorRecord> for Author {
+    fn from(AuthorRecord(author, socials): AuthorRecord) -> Self {
+        Author {
+            name: author.name,
+            socials: socials.map(|socials| Social {
+                twitter: socials.x,
+                youtube: socials.youtube,
+                instagram: socials.instagram,
+                linkedin: socials.linkedin,
+                mastodon: socials.mastodon,
+                bluesky: socials.bluesky,
+            }),
+        }
+    }
+}
+
+/// List every author with their socials, ordered by name
+///
+/// Convert the pairs into API-shaped authors with
+/// `Author::from(AuthorRecord::from(pair))`.
+pub async fn list_authors_with_socials(
+    db: &DatabaseConnection,
+) -> Result<Vec<(authors::Model, Option<author_socials::Model>)>, DbErr> {
+    authors::Entity::find()
+        .find_also_related(author_socials::Entity)
+        .order_by_asc(authors::Column::Name)
+        .order_by_asc(authors::Column::Id)
+        .all(db)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, Database, Set};
+
+    /// A migrated in-memory database with three authors, one with socials
+    async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        for (id, name) in [(1, "Zoe Park"), (2, "Ada Lee"), (3, "Ben Ito")] {
+            authors::ActiveModel {
+                id: Set(id),
+                name: Set(name.to_string()),
+            }
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+        author_socials::ActiveModel {
+            author_id: Set(1),
+            x: Set(Some("https://x.com/zoe".to_string())),
+            youtube: Set(None),
+            instagram: Set(None),
+            linkedin: Set(None),
+            mastodon: Set(Some("https://example.social/@zoe".to_string())),
+            bluesky: Set(None),
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn lists_authors_by_name_with_their_socials() {
+        let db = database().await;
+        let rows = list_authors_with_socials(&db).await.unwrap();
+
+        let names: Vec<&str> = rows
+            .iter()
+            .map(|(author, _)| author.name.as_str())
+            .collect();
+        assert_eq!(names, ["Ada Lee", "Ben Ito", "Zoe Park"]);
+        assert!(rows[0].1.is_none());
+        assert!(rows[1].1.is_none());
+        assert_eq!(rows[2].1.as_ref().unwrap().author_id, 1);
+    }
+
+    #[tokio::test]
+    async fn rows_convert_to_api_authors() {
+        let db = database().await;
+        let authors: Vec<Author> = list_authors_with_socials(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|pair| Author::from(AuthorRecord::from(pair)))
+            .collect();
+
+        assert_eq!(authors[0].name, "Ada Lee");
+        assert!(authors[0].socials.is_none());
+        let socials = authors[2].socials.as_ref().unwrap();
+        assert_eq!(socials.twitter.as_deref(), Some("https://x.com/zoe"));
+        assert_eq!(
+            socials.mastodon.as_deref(),
+            Some("https://example.social/@zoe")
+        );
+        assert_eq!(socials.youtube, None);
+    }
+}
@@ -0,0 +1,59 @@
+//! Paginated reads of the articles, blogs and reports tables
+
+use entities::{articles, blogs, reports};
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryOrder, Select};
+
+/// Fetch one page of a query together with the total number of rows
+///
+/// `page` is zero-based. A `per_page` of 0 is treated as 1.
+async fn fetch_page<E>(
+    select: Select<E>,
+    db: &DatabaseConnection,
+    page: u64,
+    per_page: u64,
+) -> Result<(Vec<E::Model>, u64), DbErr>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+{
+    let paginator = select.paginate(db, per_page.max(1));
+    let total = paginator.num_items().await?;
+    let items = paginator.fetch_page(page).await?;
+    Ok((items, total))
+}
+
+/// List a page of articles, newest first, with the total article count
+pub async fn list_articles(
+    db: &DatabaseConnection,
+    page: u64,
+    per_page: u64,
+) -> Result<(Vec<articles::Model>, u64), DbErr> {
+    let select = articles::Entity::find()
+        .order_by_desc(articles::Column::PublishedAt)
+        .order_by_desc(articles::Column::Id);
+    fetch_page(select, db, page, per_page).await
+}
+
+/// List a page of blogs, newest first, with the total blog count
+pub async fn list_blogs(
+    db: &DatabaseConnection,
+    page: u64,
+    per_page: u64,
+) -> Result<(Vec<blogs::Model>, u64), DbErr> {
+    let select = blogs::Entity::find()
+        .order_by_desc(blogs::Column::PublishedAt)
+        .order_by_desc(blogs::Column::Id);
+    fetch_page(select, db, page, per_page).await
+}
+
+/// List a page of reports, newest first, with the total report count
+pub async fn list_reports(
+    db: &DatabaseConnection,
+    page: u64,
+    per_page: u64,
+) -> Result<(Vec<reports::Model>, u64), DbErr> {
+    let select = reports::Entity::find()
+        .order_by_desc(reports::Column::PublishedAt)
+        .order_by_desc(reports::Column::Id);
+    fetch_page(select, db, page, per_page).await
+}
@@ -0,0 +1,337 @@
+//! Filling launch and event rows from their full Launch Library 2 records
+//!
+//! Articles only carry the LL2 ID and provider of the launches and events
+//! they mention; the helpers here add the LL2 `name` to the stored rows.
+//! Events are matched on `external_id`; launches are fetched by their
+//! `launch_uuid` and updated by row ID.
+
+use client::event::EventDetail;
+use client::launch::LaunchDetail;
+use client::provider::Provider;
+use client::{BoxError, RateLimiter, SpaceDevsClient};
+use entities::{events, launches};
+use futures::stream::{self, TryStreamExt};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, QueryFilter,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Store an event's LL2 name, creating the event row if needed
+///
+/// The row is found by `external_id`; a row without a provider gets
+/// Launch Library 2. Returns the row ID.
+pub async fn store_event_detail(
+    db: &impl ConnectionTrait,
+    detail: &EventDetail,
+) -> Result<i32, DbErr> {
+    let external_id = detail.id as i32;
+    match events::Entity::find()
+        .filter(events::Column::ExternalId.eq(external_id))
+        .one(db)
+        .await?
+    {
+        Some(existing) => {
+            let id = existing.id;
+            let had_provider = existing.provider.is_some();
+            let mut event = existing.into_active_model();
+            event.name = Set(Some(detail.name.clone()));
+            if !had_provider {
+                event.provider = Set(Some(Provider::LaunchLibrary2.to_string()));
+            }
+            event.update(db).await?;
+            Ok(id)
+        }
+        None => Ok(events::Entity::insert(events::ActiveModel {
+            external_id: Set(Some(external_id)),
+            name: Set(Some(detail.name.clone())),
+            provider: Set(Some(Provider::LaunchLibrary2.to_string())),
+            ..Default::default()
+        })
+        .exec(db)
+        .await?
+        .last_insert_id),
+    }
+}
+
+/// Store a launch's LL2 name and UUID on the launch row with ID
+/// `launch_id`
+///
+/// A row without a provider gets Launch Library 2. Fails with
+/// `DbErr::RecordNotFound` if there is no such row.
+pub async fn store_launch_detail(
+    db: &impl ConnectionTrait,
+    launch_id: i32,
+    detail: &LaunchDetail,
+) -> Result<(), DbErr> {
+    let existing = launches::Entity::find_by_id(launch_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("launch {}", launch_id)))?;
+    let had_provider = existing.provider.is_some();
+    let mut launch = existing.into_active_model();
+    launch.name = Set(Some(detail.name.clone()));
+    launch.launch_uuid = Set(Some(detail.id.clone()));
+    if !had_provider {
+        launch.provider = Set(Some(Provider::LaunchLibrary2.to_string()));
+    }
+    launch.update(db).await?;
+    Ok(())
+}
+
+/// Fetch and store the LL2 record of every stored event without a name
+///
+/// Events are fetched one at a time and stored as they arrive, so an
+/// error leaves the events enriched so far in place. Returns the number
+/// of events updated.
+pub async fn enrich_events(
+    client: &SpaceDevsClient,
+    db: &DatabaseConnection,
+) -> Result<u64, BoxError> {
+    let pending = events::Entity::find()
+        .filter(events::Column::Name.is_null())
+        .filter(events::Column::ExternalId.is_not_null())
+        .all(db)
+        .await?;
+
+    let mut updated = 0;
+    for event in pending {
+        let Some(external_id) = event.external_id else {
+            continue;
+        };
+        let detail = client.get_event_detail(external_id as u32).await?;
+        store_event_detail(db, &detail).await?;
+        updated += 1;
+    }
+    Ok(updated)
+}
+
+/// Fetch and store the LL2 record of every stored launch without a name
+///
+/// Runs up to `concurrency` fetches at once (at least one), started no
+/// faster than `rate` requests per second. Each launch is stored as soon
+/// as its record arrives, and launches that already have a name are
+/// skipped, so a run stopped by an error or rate-limit response can be
+/// repeated to pick up where it left off. Returns the number of launches
+/// updated.
+///
+/// Fails before any request if `rate` is not a positive, finite number.
+pub async fn enrich_launches(
+    client: &SpaceDevsClient,
+    db: &DatabaseConnection,
+    concurrency: usize,
+    rate: f64,
+) -> Result<u64, BoxError> {
+    if !(rate.is_finite() && rate > 0.0) {
+        return Err(format!(
+            "Invalid rate {}: expected a positive number of requests per second",
+            rate
+        )
+        .into());
+    }
+
+    let pending = launches::Entity::find()
+        .filter(launches::Column::Name.is_null())
+        .filter(launches::Column::LaunchUuid.is_not_null())
+        .all(db)
+        .await?;
+
+    let limiter = RateLimiter::new(rate);
+    let enriched = AtomicU64::new(0);
+    stream::iter(pending.into_iter().map(Ok::<_, BoxError>))
+        .try_for_each_concurrent(concurrency.max(1), |launch| {
+            let limiter = &limiter;
+            let enriched = &enriched;
+            async move {
+                let Some(uuid) = launch.launch_uuid.as_deref() else {
+                    return Ok(());
+                };
+                limiter.acquire().await;
+                let detail = client.get_launch_detail(uuid).await?;
+                store_launch_detail(db, launch.id, &detail).await?;
+                enriched.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        })
+        .await?;
+    Ok(enriched.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use client::{Interaction, RESTClient, RecordingTransport};
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    async fn database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        db
+    }
+
+    fn launch_detail() -> LaunchDetail {
+        serde_json::from_value(json!({
+            "id": "e3df2ecd-c239-472f-95e4-2b89b4f75800",
+            "name": "Falcon 9 Block 5 | Starlink Group 10-9",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn launch_detail_stores_name_uuid_and_provider() {
+        let db = database().await;
+        // A launch linked before its UUID was known
+        let launch_id = launches::Entity::insert(launches::ActiveModel {
+            name: Set(None),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .unwrap()
+        .last_insert_id;
+
+        let detail = launch_detail();
+        store_launch_detail(&db, launch_id, &detail).await.unwrap();
+
+        let stored = launches::Entity::find_by_id(launch_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.name.as_deref(), Some(detail.name.as_str()));
+        assert_eq!(stored.launch_uuid.as_deref(), Some(detail.id.as_str()));
+        assert_eq!(stored.provider.as_deref(), Some("Launch Library 2"));
+    }
+
+    #[tokio::test]
+    async fn launch_detail_needs_an_existing_row() {
+        let db = database().await;
+        let error = store_launch_detail(&db, 42, &launch_detail())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, DbErr::RecordNotFound(_)));
+    }
+
+    /// A client whose LL2 requests replay `records`, each served once as
+    /// the response to `GET http://ll2.test/<path>`
+    fn replaying_client(
+        dir: &tempfile::TempDir,
+        records: &[(&str, serde_json::Value)],
+    ) -> SpaceDevsClient {
+        let interactions: Vec<Interaction> = records
+            .iter()
+            .map(|(path, body)| Interaction {
+                method: "GET".to_string(),
+                url: format!("http://ll2.test/{}", path),
+                status: 200,
+                headers: vec![("content-type".to_string(), "application/json".to_string())],
+                body: body.to_string(),
+            })
+            .collect();
+        let cassette = dir.path().join("ll2.json");
+        std::fs::write(&cassette, serde_json::to_string(&interactions).unwrap()).unwrap();
+        let transport = RecordingTransport::replay(cassette).unwrap();
+        SpaceDevsClient::with_base_url("http://snapi.test").with_data_rest_client(
+            RESTClient::new("http://ll2.test").with_transport(Arc::new(transport)),
+        )
+    }
+
+    async fn insert_launch(db: &DatabaseConnection, uuid: &str, name: Option<&str>) {
+        launches::Entity::insert(launches::ActiveModel {
+            launch_uuid: Set(Some(uuid.to_string())),
+            name: Set(name.map(str::to_string)),
+            ..Default::default()
+        })
+        .exec(db)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_event(db: &DatabaseConnection, external_id: i32, name: Option<&str>) {
+        events::Entity::insert(events::ActiveModel {
+            external_id: Set(Some(external_id)),
+            name: Set(name.map(str::to_string)),
+            ..Default::default()
+        })
+        .exec(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn enrich_launches_rejects_a_bad_rate() {
+        let db = database().await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = replaying_client(&dir, &[]);
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let error = enrich_launches(&client, &db, 1, rate).await.unwrap_err();
+            assert!(error.to_string().starts_with("Invalid rate"), "{error}");
+        }
+    }
+
+    #[tokio::test]
+    async fn enrich_launches_fills_only_unnamed_launches() {
+        let db = database().await;
+        insert_launch(&db, "aaaa", Some("Already named")).await;
+        insert_launch(&db, "bbbb", None).await;
+        insert_launch(&db, "cccc", None).await;
+        let dir = tempfile::tempdir().unwrap();
+        // Only the unnamed launches are in the cassette, so fetching the
+        // named one would fail the run
+        let client = replaying_client(
+            &dir,
+            &[
+                (
+                    "launches/bbbb/",
+                    json!({ "id": "bbbb", "name": "Launch B" }),
+                ),
+                (
+                    "launches/cccc/",
+                    json!({ "id": "cccc", "name": "Launch C" }),
+                ),
+            ],
+        );
+
+        assert_eq!(enrich_launches(&client, &db, 2, 1000.0).await.unwrap(), 2);
+        let mut names: Vec<String> = launches::Entity::find()
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|launch| launch.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["Already named", "Launch B", "Launch C"]);
+
+        // A second run finds nothing left to do and sends no requests
+        assert_eq!(enrich_launches(&client, &db, 2, 1000.0).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn enrich_events_fills_only_unnamed_events() {
+        let db = database().await;
+        insert_event(&db, 1, Some("Already named")).await;
+        insert_event(&db, 2, None).await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = replaying_client(
+            &dir,
+            &[("events/2/", json!({ "id": 2, "name": "Event 2" }))],
+        );
+
+        assert_eq!(enrich_events(&client, &db).await.unwrap(), 1);
+        let stored = events::Entity::find()
+            .filter(events::Column::ExternalId.eq(2))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.name.as_deref(), Some("Event 2"));
+        assert_eq!(stored.provider.as_deref(), Some("Launch Library 2"));
+
+        assert_eq!(enrich_events(&client, &db).await.unwrap(), 0);
+    }
+}
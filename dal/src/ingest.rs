@@ -0,0 +1,464 @@
+//! Conversion of fetched content into `ActiveModel`s for insertion
+//!
+//! The API's field names are mapped onto column names through a
+//! [`FieldMap`], so the database can use its own naming without the
+//! conversion code changing. Fields without a mapping go to the column of
+//! the same name; fields with no matching column are ignored.
+
+use client::RESTClient;
+use client::article::Article;
+use client::blog::Blog;
+use client::report::Report;
+use entities::{articles, blogs, reports};
+use futures::TryStreamExt;
+use sea_orm::prelude::ChronoDateTimeWithTimeZone;
+use sea_orm::sea_query::{ColumnType, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityName, EntityTrait, IdenStatic,
+    IntoActiveModel, Iterable, PrimaryKeyToColumn, QueryFilter, QuerySelect, TransactionTrait,
+    Value,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value as Json;
+use std::collections::HashMap;
+
+/// Renames from API field names to column names, per table
+///
+/// Loaded from `[field_map.<table>]` sections of the executor's TOML
+/// configuration, each mapping an API field to a column. For a database
+/// whose `articles` table names the `news_site` column `source`:
+///
+/// ```toml
+/// [field_map.articles]
+/// news_site = "source"
+/// ```
+///
+/// Every column named must exist in its table; [`to_active_model`] fails
+/// otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct FieldMap {
+    tables: HashMap<String, HashMap<String, String>>,
+}
+
+impl FieldMap {
+    /// Create an empty map, under which every field keeps its name
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the `[field_map]` sections of a TOML configuration file
+    pub fn from_config_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_config_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Read the `[field_map]` sections of TOML configuration text
+    pub fn from_config_str(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: toml::Value = toml::from_str(contents)?;
+        Self::from_toml_value(&config)
+    }
+
+    /// Read the `[field_map]` sections of a parsed TOML configuration
+    pub fn from_toml_value(config: &toml::Value) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut field_map = Self::new();
+        let Some(sections) = config.get("field_map").and_then(|v| v.as_table()) else {
+            return Ok(field_map);
+        };
+
+        for (table, renames) in sections {
+            let renames = renames
+                .as_table()
+                .ok_or_else(|| format!("field_map.{} must be a table", table))?;
+            for (field, column) in renames {
+                let column = column.as_str().ok_or_else(|| {
+                    format!("field_map.{}.{} must be a column name", table, field)
+                })?;
+                field_map.rename(table, field, column);
+            }
+        }
+
+        Ok(field_map)
+    }
+
+    /// Map the API field `field` of `table` onto `column`
+    pub fn rename(&mut self, table: &str, field: &str, column: &str) {
+        self.tables
+            .entry(table.to_string())
+            .or_default()
+            .insert(field.to_string(), column.to_string());
+    }
+
+    /// Column that receives the API field `field` of `table`
+    pub fn column_for<'a>(&'a self, table: &str, field: &'a str) -> &'a str {
+        self.tables
+            .get(table)
+            .and_then(|renames| renames.get(field))
+            .map_or(field, String::as_str)
+    }
+}
+
+/// Build an `ActiveModel` from a serializable item, renaming its fields
+///
+/// Only top-level scalar fields are converted; nested lists such as
+/// `authors` have their own tables. Columns with no matching field stay
+/// `NotSet`. A field renamed to a column the table doesn't have is an
+/// error rather than being dropped, and a renamed field takes precedence
+/// over a field of the same name as its column.
+pub fn to_active_model<A>(item: &impl Serialize, field_map: &FieldMap) -> Result<A, DbErr>
+where
+    A: ActiveModelTrait,
+{
+    let table = A::Entity::default().table_name();
+    let Json::Object(fields) =
+        serde_json::to_value(item).map_err(|e| DbErr::Json(e.to_string()))?
+    else {
+        return Err(DbErr::Json(format!("{} item is not a JSON object", table)));
+    };
+
+    let known: Vec<&str> = <A::Entity as EntityTrait>::Column::iter()
+        .map(|column| column.as_str())
+        .collect();
+    let mut columns: HashMap<&str, &Json> = HashMap::new();
+    for (field, value) in &fields {
+        let column = field_map.column_for(table, field);
+        if column == field.as_str() {
+            // A renamed field takes precedence over one already named like its column
+            columns.entry(column).or_insert(value);
+        } else if known.contains(&column) {
+            columns.insert(column, value);
+        } else {
+            return Err(DbErr::Custom(format!(
+                "field_map.{}.{}: {} has no column '{}'",
+                table, field, table, column
+            )));
+        }
+    }
+
+    let mut model = <A as ActiveModelTrait>::default();
+    for column in <A::Entity as EntityTrait>::Column::iter() {
+        if let Some(value) = columns.get(column.as_str()) {
+            let value = json_to_column_value(column.def().get_column_type(), value)
+                .map_err(|e| DbErr::Type(format!("{}.{}: {}", table, column.as_str(), e)))?;
+            model.try_set(column, value)?;
+        }
+    }
+    Ok(model)
+}
+
+/// Build an articles `ActiveModel`, applying the `articles` field map
+pub fn article_active_model(
+    article: &Article,
+    field_map: &FieldMap,
+) -> Result<articles::ActiveModel, DbErr> {
+    to_active_model(article, field_map)
+}
+
+/// Build a blogs `ActiveModel`, applying the `blogs` field map
+pub fn blog_active_model(blog: &Blog, field_map: &FieldMap) -> Result<blogs::ActiveModel, DbErr> {
+    to_active_model(blog, field_map)
+}
+
+/// Build a reports `ActiveModel`, applying the `reports` field map
+pub fn report_active_model(
+    report: &Report,
+    field_map: &FieldMap,
+) -> Result<reports::ActiveModel, DbErr> {
+    to_active_model(report, field_map)
+}
+
+/// Keep only the articles that are not stored yet or changed since
+///
+/// Looks up all IDs in one `id IN (...)` query. An article is kept when its
+/// ID is absent, or when its `updated_at` is later than the stored one. An
+/// article whose `updated_at` cannot be parsed is kept, since it cannot be
+/// shown to be unchanged.
+pub async fn filter_new(db: &DatabaseConnection, items: &[Article]) -> Result<Vec<Article>, DbErr> {
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<i32> = items.iter().map(|article| article.id as i32).collect();
+    let stored: HashMap<i32, sea_orm::prelude::DateTime> = articles::Entity::find()
+        .select_only()
+        .column(articles::Column::Id)
+        .column(articles::Column::UpdatedAt)
+        .filter(articles::Column::Id.is_in(ids))
+        .into_tuple()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(items
+        .iter()
+        .filter(|article| match stored.get(&(article.id as i32)) {
+            None => true,
+            Some(stored_updated_at) => {
+                ChronoDateTimeWithTimeZone::parse_from_rfc3339(&article.updated_at)
+                    .map_or(true, |updated_at| {
+                        updated_at.naive_utc() > *stored_updated_at
+                    })
+            }
+        })
+        .cloned()
+        .collect())
+}
+
+/// Fetch every page of `endpoint` and upsert the items in batches
+///
+/// Pages are fetched one at a time and items are converted with
+/// [`to_active_model`], so memory holds at most one batch plus one page
+/// regardless of how many items the endpoint has. Each batch of up to
+/// `batch_size` items is upserted by primary key in its own transaction
+/// and committed before the next page is fetched, so a failure part way
+/// through keeps every batch committed before it. Returns the number of
+/// items ingested.
+///
+/// A batch is a single multi-row `INSERT`; keep `batch_size` times the
+/// number of columns under the database's bind-parameter limit.
+pub async fn ingest_stream<T, A>(
+    client: &RESTClient,
+    endpoint: &str,
+    query: &HashMap<String, String>,
+    db: &DatabaseConnection,
+    field_map: &FieldMap,
+    batch_size: usize,
+) -> Result<u64, Box<dyn std::error::Error>>
+where
+    T: DeserializeOwned + Serialize + Send + 'static,
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let batch_size = batch_size.max(1);
+    let mut pages = std::pin::pin!(client.stream_pages::<T>(endpoint, query));
+    let mut batch: Vec<A> = Vec::with_capacity(batch_size);
+    let mut ingested = 0;
+
+    while let Some(page) = pages.try_next().await? {
+        for item in &page {
+            batch.push(to_active_model(item, field_map)?);
+            if batch.len() == batch_size {
+                ingested += upsert_batch(db, std::mem::take(&mut batch)).await?;
+            }
+        }
+    }
+    ingested += upsert_batch(db, batch).await?;
+
+    Ok(ingested)
+}
+
+/// Upsert `models` by primary key in one transaction
+///
+/// Only the columns set on the first model are updated on conflict, so
+/// columns the API doesn't provide keep their stored values.
+async fn upsert_batch<A>(db: &DatabaseConnection, models: Vec<A>) -> Result<u64, DbErr>
+where
+    A: ActiveModelTrait + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    let Some(first) = models.first() else {
+        return Ok(0);
+    };
+    let count = models.len() as u64;
+
+    let primary_key: Vec<_> = <A::Entity as EntityTrait>::PrimaryKey::iter()
+        .map(|key| key.into_column())
+        .collect();
+    let updated: Vec<_> = <A::Entity as EntityTrait>::Column::iter()
+        .filter(|column| !first.is_not_set(*column))
+        .filter(|column| {
+            !primary_key
+                .iter()
+                .any(|key| key.as_str() == column.as_str())
+        })
+        .collect();
+    let mut on_conflict = OnConflict::columns(primary_key);
+    if updated.is_empty() {
+        on_conflict.do_nothing();
+    } else {
+        on_conflict.update_columns(updated);
+    }
+
+    let txn = db.begin().await?;
+    A::Entity::insert_many(models)
+        .on_conflict(on_conflict)
+        .exec_without_returning(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(count)
+}
+
+/// Convert a JSON value into a database value of the column's type
+///
+/// Timestamps are parsed as RFC 3339 and stored as naive UTC.
+fn json_to_column_value(column_type: &ColumnType, value: &Json) -> Result<Value, String> {
+    let mismatch = || format!("cannot store {} in a {:?} column", value, column_type);
+    let converted = match column_type {
+        ColumnType::Integer => match value {
+            Json::Null => Value::from(None::<i32>),
+            _ => Value::from(
+                value
+                    .as_i64()
+                    .and_then(|v| i32::try_from(v).ok())
+                    .ok_or_else(mismatch)?,
+            ),
+        },
+        ColumnType::BigInteger => match value {
+            Json::Null => Value::from(None::<i64>),
+            _ => Value::from(value.as_i64().ok_or_else(mismatch)?),
+        },
+        ColumnType::Boolean => match value {
+            Json::Null => Value::from(None::<bool>),
+            _ => Value::from(value.as_bool().ok_or_else(mismatch)?),
+        },
+        ColumnType::String(_) | ColumnType::Text | ColumnType::Char(_) => match value {
+            Json::Null => Value::from(None::<String>),
+            Json::String(s) => Value::from(s.clone()),
+            _ => return Err(mismatch()),
+        },
+        ColumnType::DateTime | ColumnType::Timestamp => match value {
+            Json::Null => Value::from(None::<sea_orm::prelude::DateTime>),
+            _ => {
+                let timestamp = value.as_str().ok_or_else(mismatch)?;
+                let parsed = ChronoDateTimeWithTimeZone::parse_from_rfc3339(timestamp)
+                    .map_err(|e| format!("invalid timestamp '{}': {}", timestamp, e))?;
+                Value::from(parsed.naive_utc())
+            }
+        },
+        _ => return Err(format!("unsupported column type {:?}", column_type)),
+    };
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, Database, Set};
+    use serde_json::json;
+
+    fn article(id: u32, updated_at: &str) -> Article {
+        serde_json::from_value(json!({
+            "id": id,
+            "title": format!("Article {id}"),
+            "url": format!("https://example.com/{id}"),
+            "news_site": "Example News",
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": updated_at,
+        }))
+        .unwrap()
+    }
+
+    /// A migrated in-memory database holding `stored`
+    async fn database(stored: &[Article]) -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        for article in stored {
+            article_active_model(article, &FieldMap::new())
+                .unwrap()
+                .insert(&db)
+                .await
+                .unwrap();
+        }
+        db
+    }
+
+    #[tokio::test]
+    async fn keeps_only_new_and_updated_articles() {
+        let db = database(&[
+            article(1, "2026-10-01T12:00:00Z"),
+            article(2, "2026-10-01T12:00:00Z"),
+            article(3, "2026-10-01T12:00:00Z"),
+            article(4, "2026-10-01T12:00:00Z"),
+        ])
+        .await;
+
+        let items = [
+            article(5, "2026-10-01T12:00:00Z"),
+            article(1, "2026-10-01T12:00:00Z"),
+            article(2, "2026-10-02T08:00:00Z"),
+            article(3, "2026-09-30T12:00:00Z"),
+            article(4, "yesterday"),
+        ];
+        let ids: Vec<u32> = filter_new(&db, &items)
+            .await
+            .unwrap()
+            .iter()
+            .map(|article| article.id)
+            .collect();
+        // New, newer and unparseable are kept, in the order given
+        assert_eq!(ids, [5, 2, 4]);
+    }
+
+    #[tokio::test]
+    async fn offsets_are_compared_in_utc() {
+        let db = database(&[article(1, "2026-10-01T12:00:00Z")]).await;
+        let same_instant = article(1, "2026-10-01T14:00:00+02:00");
+        let later = article(1, "2026-10-01T13:30:00+01:00");
+        let kept = filter_new(&db, &[same_instant, later]).await.unwrap();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].updated_at, "2026-10-01T13:30:00+01:00");
+    }
+
+    #[tokio::test]
+    async fn empty_input_needs_no_query() {
+        let db = database(&[]).await;
+        assert!(filter_new(&db, &[]).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn reads_field_map_sections() {
+        let field_map = FieldMap::from_config_str(
+            r#"
+            [config]
+            max_limit = 100
+
+            [field_map.articles]
+            news_site = "source"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(field_map.column_for("articles", "news_site"), "source");
+        assert_eq!(field_map.column_for("articles", "title"), "title");
+        assert_eq!(field_map.column_for("blogs", "news_site"), "news_site");
+
+        let empty = FieldMap::from_config_str("[config]\nmax_limit = 100").unwrap();
+        assert_eq!(empty.column_for("articles", "news_site"), "news_site");
+    }
+
+    #[test]
+    fn rejects_malformed_field_map_sections() {
+        let error = FieldMap::from_config_str("[field_map]\narticles = 1").unwrap_err();
+        assert_eq!(error.to_string(), "field_map.articles must be a table");
+
+        let error = FieldMap::from_config_str("[field_map.articles]\nnews_site = 1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "field_map.articles.news_site must be a column name"
+        );
+    }
+
+    #[test]
+    fn renamed_fields_fill_their_column() {
+        let mut field_map = FieldMap::new();
+        field_map.rename("articles", "news_site", "summary");
+        let model = article_active_model(&article(1, "2026-10-01T12:00:00Z"), &field_map).unwrap();
+        assert_eq!(model.summary, Set(Some("Example News".to_string())));
+        assert!(model.news_site.is_not_set());
+        assert_eq!(model.title, Set("Article 1".to_string()));
+    }
+
+    #[test]
+    fn renaming_to_a_missing_column_fails() {
+        let mut field_map = FieldMap::new();
+        field_map.rename("articles", "news_site", "source");
+        let error =
+            article_active_model(&article(1, "2026-10-01T12:00:00Z"), &field_map).unwrap_err();
+        assert!(matches!(error, DbErr::Custom(_)), "{error:?}");
+        assert!(
+            error
+                .to_string()
+                .contains("articles has no column 'source'")
+        );
+    }
+}
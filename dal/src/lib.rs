@@ -0,0 +1,11 @@
+//! Data access layer over the SeaORM entities
+//!
+//! Query helpers that read the ingested Spaceflight News data back out of
+//! the database, and conversions that prepare fetched data for insertion.
+
+pub mod authors;
+pub mod content;
+pub mod enrich;
+pub mod ingest;
+pub mod reconcile;
+pub mod relations;
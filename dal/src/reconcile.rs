@@ -0,0 +1,237 @@
+//! Comparison of upstream content with what is stored locally
+
+use crate::ingest::{FieldMap, to_active_model};
+use client::article::Article;
+use client::blog::Blog;
+use client::report::Report;
+use client::{BoxError, SpaceDevsClient};
+use entities::{articles, blogs, reports};
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, DbErr, EntityName, EntityTrait, IdenStatic,
+    IntoActiveModel, Iterable, Value,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// API fields whose stored values are compared, besides the ID
+const COMPARED_FIELDS: [&str; 4] = ["updated_at", "title", "url", "summary"];
+
+/// How the items of an endpoint differ between the API and the database
+///
+/// Each list holds IDs in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Items the API has that are not stored
+    pub only_upstream: Vec<i32>,
+    /// Stored items the API no longer has
+    pub only_local: Vec<i32>,
+    /// Items in both whose `updated_at`, title, URL or summary differ
+    pub differing: Vec<i32>,
+}
+
+/// The values of the compared columns of an item, in `COMPARED_FIELDS` order
+type Fingerprint = Vec<Option<Value>>;
+
+/// Compare every item of `endpoint` in the API with its table
+///
+/// `endpoint` is `"articles"`, `"blogs"` or `"reports"`. Fetches all pages
+/// from the API, in pages of the client's maximum limit, then reads the
+/// whole table in one query. Upstream items are converted with
+/// [`to_active_model`] under `field_map`, so each field is compared with
+/// the column it would be stored in; an item that cannot be converted
+/// fails the comparison. Author, launch and event links are not compared.
+pub async fn reconcile(
+    client: &SpaceDevsClient,
+    db: &DatabaseConnection,
+    endpoint: &str,
+    field_map: &FieldMap,
+) -> Result<Reconciliation, BoxError> {
+    let (upstream, local) = match endpoint {
+        "articles" => (
+            fetch_upstream::<Article, articles::ActiveModel>(client, endpoint, field_map).await?,
+            stored::<articles::ActiveModel>(db, field_map).await?,
+        ),
+        "blogs" => (
+            fetch_upstream::<Blog, blogs::ActiveModel>(client, endpoint, field_map).await?,
+            stored::<blogs::ActiveModel>(db, field_map).await?,
+        ),
+        "reports" => (
+            fetch_upstream::<Report, reports::ActiveModel>(client, endpoint, field_map).await?,
+            stored::<reports::ActiveModel>(db, field_map).await?,
+        ),
+        other => return Err(format!("cannot reconcile unknown endpoint '{}'", other).into()),
+    };
+    Ok(compare(&upstream, &local))
+}
+
+/// Sort the IDs of two fingerprinted item sets into a reconciliation
+fn compare<F: PartialEq>(upstream: &HashMap<i32, F>, local: &HashMap<i32, F>) -> Reconciliation {
+    let mut reconciliation = Reconciliation::default();
+    for (id, fingerprint) in upstream {
+        match local.get(id) {
+            None => reconciliation.only_upstream.push(*id),
+            Some(stored) if stored != fingerprint => reconciliation.differing.push(*id),
+            Some(_) => {}
+        }
+    }
+    reconciliation.only_local = local
+        .keys()
+        .filter(|id| !upstream.contains_key(id))
+        .copied()
+        .collect();
+
+    reconciliation.only_upstream.sort_unstable();
+    reconciliation.only_local.sort_unstable();
+    reconciliation.differing.sort_unstable();
+    reconciliation
+}
+
+/// Fetch every item of `endpoint` and fingerprint it as it would be stored
+async fn fetch_upstream<T, A>(
+    client: &SpaceDevsClient,
+    endpoint: &str,
+    field_map: &FieldMap,
+) -> Result<HashMap<i32, Fingerprint>, BoxError>
+where
+    T: DeserializeOwned + Serialize + Send + 'static,
+    A: ActiveModelTrait,
+{
+    let items: Vec<T> = client
+        .rest()
+        .get_all(&format!(
+            "{}/?limit={}",
+            endpoint,
+            client.rest().max_limit()
+        ))
+        .await?;
+    let mut fingerprints = HashMap::with_capacity(items.len());
+    for item in &items {
+        let (id, fingerprint) = fingerprint(&to_active_model::<A>(item, field_map)?, field_map)?;
+        fingerprints.insert(id, fingerprint);
+    }
+    Ok(fingerprints)
+}
+
+/// Read and fingerprint every stored row of a content table
+async fn stored<A>(
+    db: &DatabaseConnection,
+    field_map: &FieldMap,
+) -> Result<HashMap<i32, Fingerprint>, DbErr>
+where
+    A: ActiveModelTrait,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    A::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| fingerprint(&row.into_active_model(), field_map))
+        .collect()
+}
+
+/// The ID and compared column values of a model
+///
+/// Each API field is looked up in the column `field_map` stores it in.
+fn fingerprint<A>(model: &A, field_map: &FieldMap) -> Result<(i32, Fingerprint), DbErr>
+where
+    A: ActiveModelTrait,
+{
+    let table = A::Entity::default().table_name();
+    let value = |field: &str| {
+        let name = field_map.column_for(table, field);
+        <A::Entity as EntityTrait>::Column::iter()
+            .find(|column| column.as_str() == name)
+            .map(|column| model.get(column).into_value())
+            .ok_or_else(|| DbErr::Custom(format!("{} has no column '{}'", table, name)))
+    };
+
+    let id = match value("id")? {
+        Some(Value::Int(Some(id))) => id,
+        other => {
+            return Err(DbErr::Type(format!(
+                "{}: expected an integer ID, got {:?}",
+                table, other
+            )));
+        }
+    };
+    let fingerprint = COMPARED_FIELDS
+        .iter()
+        .map(|field| value(field))
+        .collect::<Result<_, _>>()?;
+    Ok((id, fingerprint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::article_active_model;
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::Database;
+    use serde_json::json;
+
+    fn article(summary: &str) -> Article {
+        serde_json::from_value(json!({
+            "id": 1,
+            "title": "Launch recap",
+            "url": "https://example.com/recap",
+            "news_site": "Example News",
+            "summary": summary,
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T14:00:00+02:00",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn compare_sorts_ids_by_where_they_are() {
+        let upstream = HashMap::from([(5, "a"), (1, "b"), (3, "c"), (4, "d"), (2, "e")]);
+        let local = HashMap::from([(4, "d"), (3, "changed"), (9, "x"), (1, "old"), (7, "y")]);
+        assert_eq!(
+            compare(&upstream, &local),
+            Reconciliation {
+                only_upstream: vec![2, 5],
+                only_local: vec![7, 9],
+                differing: vec![1, 3],
+            }
+        );
+        assert_eq!(
+            compare::<&str>(&HashMap::new(), &HashMap::new()),
+            Reconciliation::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn fingerprints_follow_field_map_renames() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        let mut field_map = FieldMap::new();
+        field_map.rename("articles", "summary", "news_site");
+        let article = article("Booster caught");
+        article_active_model(&article, &field_map)
+            .unwrap()
+            .insert(&db)
+            .await
+            .unwrap();
+
+        // Stored as the field map directs, with the offset normalized to UTC
+        let upstream = HashMap::from([fingerprint(
+            &article_active_model(&article, &field_map).unwrap(),
+            &field_map,
+        )
+        .unwrap()]);
+        let local = stored::<articles::ActiveModel>(&db, &field_map)
+            .await
+            .unwrap();
+        assert_eq!(compare(&upstream, &local), Reconciliation::default());
+
+        // Ignoring the rename compares the summary with the wrong column
+        let plain = FieldMap::new();
+        let upstream =
+            HashMap::from([
+                fingerprint(&article_active_model(&article, &plain).unwrap(), &plain).unwrap(),
+            ]);
+        let local = stored::<articles::ActiveModel>(&db, &plain).await.unwrap();
+        assert_eq!(compare(&upstream, &local).differing, [1]);
+    }
+}
@@ -0,0 +1,343 @@
+//! Keeping the many-to-many join tables in step with fetched content
+//!
+//! Re-ingesting an article must drop the links to authors, launches and
+//! events it no longer lists, not only add new ones;
+//! [`sync_article_relations`] brings the join rows to exactly the
+//! article's current lists.
+//!
+//! Launches are matched on `launches.launch_uuid`, since the API
+//! identifies them by UUID; events on `events.external_id`.
+
+use client::article::Article;
+use client::event::Event;
+use client::launch::Launch;
+use entities::{article_authors, article_events, article_launches, authors, events, launches};
+use sea_orm::ActiveValue::Set;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use std::collections::HashSet;
+
+/// Make the article's author, launch and event links match `article`
+///
+/// In one transaction, creates any authors, launches and events not
+/// stored yet, inserts the links that are missing and deletes the links
+/// the article no longer lists. Returns the number of links
+/// added and removed.
+pub async fn sync_article_relations(
+    db: &DatabaseConnection,
+    article_id: i32,
+    article: &Article,
+) -> Result<(u64, u64), DbErr> {
+    let txn = db.begin().await?;
+
+    let mut wanted_authors = HashSet::new();
+    for author in &article.authors {
+        wanted_authors.insert(author_id(&txn, &author.name).await?);
+    }
+    let stored_authors: HashSet<i32> = article_authors::Entity::find()
+        .filter(article_authors::Column::ArticleId.eq(article_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|link| link.author_id)
+        .collect();
+
+    let mut wanted_launches = HashSet::new();
+    for launch in &article.launches {
+        wanted_launches.insert(launch_id(&txn, launch).await?);
+    }
+    let stored_launches: HashSet<i32> = article_launches::Entity::find()
+        .filter(article_launches::Column::ArticleId.eq(article_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|link| link.launch_id)
+        .collect();
+
+    let mut wanted_events = HashSet::new();
+    for event in &article.events {
+        wanted_events.insert(event_id(&txn, event).await?);
+    }
+    let stored_events: HashSet<i32> = article_events::Entity::find()
+        .filter(article_events::Column::ArticleId.eq(article_id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|link| link.event_id)
+        .collect();
+
+    let new_authors: Vec<_> = wanted_authors
+        .difference(&stored_authors)
+        .map(|&author_id| article_authors::ActiveModel {
+            article_id: Set(article_id),
+            author_id: Set(author_id),
+        })
+        .collect();
+    let new_launches: Vec<_> = wanted_launches
+        .difference(&stored_launches)
+        .map(|&launch_id| article_launches::ActiveModel {
+            article_id: Set(article_id),
+            launch_id: Set(launch_id),
+        })
+        .collect();
+    let new_events: Vec<_> = wanted_events
+        .difference(&stored_events)
+        .map(|&event_id| article_events::ActiveModel {
+            article_id: Set(article_id),
+            event_id: Set(event_id),
+        })
+        .collect();
+    let added = (new_authors.len() + new_launches.len() + new_events.len()) as u64;
+    if !new_authors.is_empty() {
+        article_authors::Entity::insert_many(new_authors)
+            .exec_without_returning(&txn)
+            .await?;
+    }
+    if !new_launches.is_empty() {
+        article_launches::Entity::insert_many(new_launches)
+            .exec_without_returning(&txn)
+            .await?;
+    }
+    if !new_events.is_empty() {
+        article_events::Entity::insert_many(new_events)
+            .exec_without_returning(&txn)
+            .await?;
+    }
+
+    let stale_authors: Vec<i32> = stored_authors
+        .difference(&wanted_authors)
+        .copied()
+        .collect();
+    let stale_launches: Vec<i32> = stored_launches
+        .difference(&wanted_launches)
+        .copied()
+        .collect();
+    let stale_events: Vec<i32> = stored_events.difference(&wanted_events).copied().collect();
+    let mut removed = 0;
+    if !stale_authors.is_empty() {
+        removed += article_authors::Entity::delete_many()
+            .filter(article_authors::Column::ArticleId.eq(article_id))
+            .filter(article_authors::Column::AuthorId.is_in(stale_authors))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+    }
+    if !stale_launches.is_empty() {
+        removed += article_launches::Entity::delete_many()
+            .filter(article_launches::Column::ArticleId.eq(article_id))
+            .filter(article_launches::Column::LaunchId.is_in(stale_launches))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+    }
+    if !stale_events.is_empty() {
+        removed += article_events::Entity::delete_many()
+            .filter(article_events::Column::ArticleId.eq(article_id))
+            .filter(article_events::Column::EventId.is_in(stale_events))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+    }
+
+    txn.commit().await?;
+    Ok((added, removed))
+}
+
+/// ID of the author with `name`, creating the author if needed
+///
+/// Authors have no ID in the API, so they are matched by name and new
+/// ones are numbered after the highest existing ID.
+pub async fn author_id(db: &impl ConnectionTrait, name: &str) -> Result<i32, DbErr> {
+    if let Some(author) = authors::Entity::find()
+        .filter(authors::Column::Name.eq(name))
+        .one(db)
+        .await?
+    {
+        return Ok(author.id);
+    }
+
+    let next_id = authors::Entity::find()
+        .order_by_desc(authors::Column::Id)
+        .one(db)
+        .await?
+        .map_or(1, |author| author.id + 1);
+    authors::Entity::insert(authors::ActiveModel {
+        id: Set(next_id),
+        name: Set(name.to_string()),
+    })
+    .exec_without_returning(db)
+    .await?;
+    Ok(next_id)
+}
+
+/// Row ID of the launch with the API's UUID `launch.id`, creating it if
+/// needed
+pub async fn launch_id(db: &impl ConnectionTrait, launch: &Launch) -> Result<i32, DbErr> {
+    if let Some(existing) = launches::Entity::find()
+        .filter(launches::Column::LaunchUuid.eq(&launch.id))
+        .one(db)
+        .await?
+    {
+        return Ok(existing.id);
+    }
+
+    Ok(launches::Entity::insert(launches::ActiveModel {
+        launch_uuid: Set(Some(launch.id.clone())),
+        provider: Set(Some(launch.provider.to_string())),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?
+    .last_insert_id)
+}
+
+/// Row ID of the event with the API's `event.id`, creating it if needed
+pub async fn event_id(db: &impl ConnectionTrait, event: &Event) -> Result<i32, DbErr> {
+    let external_id = event.id as i32;
+    if let Some(existing) = events::Entity::find()
+        .filter(events::Column::ExternalId.eq(external_id))
+        .one(db)
+        .await?
+    {
+        return Ok(existing.id);
+    }
+
+    Ok(events::Entity::insert(events::ActiveModel {
+        external_id: Set(Some(external_id)),
+        provider: Set(Some(event.provider.to_string())),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?
+    .last_insert_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::{FieldMap, article_active_model};
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ActiveModelTrait, Database};
+    use serde_json::json;
+
+    /// An article with the given author names, launch UUIDs and event IDs
+    fn article(authors: &[&str], launches: &[&str], events: &[u32]) -> Article {
+        serde_json::from_value(json!({
+            "id": 1,
+            "title": "Launch recap",
+            "url": "https://example.com/recap",
+            "news_site": "Example News",
+            "published_at": "2026-10-01T12:00:00Z",
+            "updated_at": "2026-10-01T12:00:00Z",
+            "authors": authors
+                .iter()
+                .map(|name| json!({ "name": name, "socials": null }))
+                .collect::<Vec<_>>(),
+            "launches": launches
+                .iter()
+                .map(|id| json!({ "launch_id": id, "provider": "Launch Library 2" }))
+                .collect::<Vec<_>>(),
+            "events": events
+                .iter()
+                .map(|id| json!({ "event_id": id, "provider": "Launch Library 2" }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    /// A migrated in-memory database holding `article`'s row
+    async fn database(article: &Article) -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        article_active_model(article, &FieldMap::new())
+            .unwrap()
+            .insert(&db)
+            .await
+            .unwrap();
+        db
+    }
+
+    /// Names, UUIDs and external IDs of everything linked to article 1
+    async fn links(db: &DatabaseConnection) -> (Vec<String>, Vec<String>, Vec<i32>) {
+        let mut author_names = Vec::new();
+        for link in article_authors::Entity::find().all(db).await.unwrap() {
+            let author = authors::Entity::find_by_id(link.author_id)
+                .one(db)
+                .await
+                .unwrap()
+                .unwrap();
+            author_names.push(author.name);
+        }
+        let mut launch_uuids = Vec::new();
+        for link in article_launches::Entity::find().all(db).await.unwrap() {
+            let launch = launches::Entity::find_by_id(link.launch_id)
+                .one(db)
+                .await
+                .unwrap()
+                .unwrap();
+            launch_uuids.push(launch.launch_uuid.unwrap());
+        }
+        let mut event_ids = Vec::new();
+        for link in article_events::Entity::find().all(db).await.unwrap() {
+            let event = events::Entity::find_by_id(link.event_id)
+                .one(db)
+                .await
+                .unwrap()
+                .unwrap();
+            event_ids.push(event.external_id.unwrap());
+        }
+        author_names.sort();
+        launch_uuids.sort();
+        event_ids.sort();
+        (author_names, launch_uuids, event_ids)
+    }
+
+    #[tokio::test]
+    async fn inserts_missing_links() {
+        let article = article(&["Ada", "Bo"], &["uuid-1"], &[7]);
+        let db = database(&article).await;
+
+        assert_eq!(
+            sync_article_relations(&db, 1, &article).await.unwrap(),
+            (4, 0)
+        );
+        assert_eq!(
+            links(&db).await,
+            (
+                vec!["Ada".to_string(), "Bo".to_string()],
+                vec!["uuid-1".to_string()],
+                vec![7]
+            )
+        );
+        // A second sync with the same lists changes nothing
+        assert_eq!(
+            sync_article_relations(&db, 1, &article).await.unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_stale_links() {
+        let before = article(&["Ada", "Bo"], &["uuid-1"], &[7]);
+        let db = database(&before).await;
+        sync_article_relations(&db, 1, &before).await.unwrap();
+
+        let after = article(&["Bo", "Cy"], &["uuid-2"], &[]);
+        assert_eq!(
+            sync_article_relations(&db, 1, &after).await.unwrap(),
+            (2, 3)
+        );
+        assert_eq!(
+            links(&db).await,
+            (
+                vec!["Bo".to_string(), "Cy".to_string()],
+                vec!["uuid-2".to_string()],
+                vec![]
+            )
+        );
+        // The launch row itself stays, for other articles to link to
+        assert_eq!(launches::Entity::find().all(&db).await.unwrap().len(), 2);
+    }
+}
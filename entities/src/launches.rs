@@ -10,6 +10,8 @@ pub struct Model {
     pub external_id: Option<i32>,
     pub name: Option<String>,
     pub provider: Option<String>,
+    #[sea_orm(unique)]
+    pub launch_uuid: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,12 +1,16 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251110_032202_create_space_devs_base;
+mod m20261016_120000_add_launch_uuid;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251110_032202_create_space_devs_base::Migration)]
+        vec![
+            Box::new(m20251110_032202_create_space_devs_base::Migration),
+            Box::new(m20261016_120000_add_launch_uuid::Migration),
+        ]
     }
 }
@@ -6,6 +6,7 @@
 //!   * authors + author_socials
 //!   * launches + events (simple reference tables)
 //!   * all many‑to‑many join tables (article_* , blog_*, report_*)
+//!
 //! It deliberately **ignores** the top‑level pagination fields
 //! (`count`, `next`, `previous`).
 
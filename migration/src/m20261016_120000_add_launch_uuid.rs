@@ -0,0 +1,61 @@
+//! Add `launches.launch_uuid` for the Launch Library 2 launch ID
+//!
+//! LL2 identifies launches by UUID, which does not fit the integer
+//! `external_id` column, so it gets a text column of its own with a
+//! unique index.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Launches::Table)
+                    .add_column(ColumnDef::new(Launches::LaunchUuid).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-launches-launch_uuid")
+                    .table(Launches::Table)
+                    .col(Launches::LaunchUuid)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-launches-launch_uuid")
+                    .table(Launches::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Launches::Table)
+                    .drop_column(Launches::LaunchUuid)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Launches {
+    Table,
+    LaunchUuid,
+}